@@ -8,6 +8,8 @@ type Answer = usize;
 const PART_ONE_SAMPLE_ANSWER: Answer = 55312;
 const PART_TWO_SAMPLE_ANSWER: Answer = 65601038650482;
 
+// is_multiple_of would need Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
 fn blink_value(value: usize) -> (usize, Option<usize>) {
     if value == 0 {
         (1, None)
@@ -75,7 +75,7 @@ fn regions(lines: &[&str]) -> Vec<Region> {
                 .positions
                 .iter()
                 .fold(HashSet::new(), |mut map, pos| {
-                    let edge_dirs = aoc::Cardinal::all().into_iter().filter(|cardinal| {
+                    let edge_dirs = aoc::Cardinal::ALL.iter().copied().filter(|cardinal| {
                         if let Some(neighbor) = grid.get_neighbor_position(*pos, *cardinal) {
                             if region.positions.contains(&neighbor) {
                                 return false;
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use aoc::Solver;
+use aoc::{BoundingBox, PointCloud, Solver};
 use itertools::Itertools;
 use log::debug;
 use regex::Regex;
@@ -91,31 +91,16 @@ impl Solver<'_, Answer> for Solution {
             "Robots: {:?}",
             robots.iter().map(|(p, v)| (p, v.len())).collect_vec()
         );
-        let mid_x = size.0 / 2;
-        let mid_y = size.1 / 2;
-        let quadrants = [
-            (0..mid_x, 0..mid_y),                       // top left
-            (0..mid_x, (mid_y + 1)..size.1),            // bottom left
-            ((mid_x + 1)..size.0, 0..mid_y),            // top right
-            ((mid_x + 1)..size.0, (mid_y + 1)..size.1), // bottom right
-        ];
-        quadrants
-            .iter()
-            .map(|(xs, ys)| {
-                let total: usize = robots
-                    .iter()
-                    .filter_map(|(p, vs)| {
-                        if xs.contains(&p.0) && ys.contains(&p.1) {
-                            Some(vs.len())
-                        } else {
-                            None
-                        }
-                    })
-                    .sum();
-                debug!("Quadrant {:?} has {} robots", (xs, ys), total);
-                total
-            })
-            .product()
+        let robots = PointCloud::new(
+            robots
+                .iter()
+                .flat_map(|(p, vs)| vs.iter().map(move |_| *p))
+                .collect(),
+        );
+        let quadrants =
+            robots.quadrant_counts(&BoundingBox::from_size(size.0 as usize, size.1 as usize));
+        debug!("Quadrants have {:?} robots", quadrants);
+        quadrants.iter().product()
     }
 
     fn solve_part_two(&self, lines: &[&str]) -> Answer {
@@ -166,10 +166,7 @@ impl Solver<'_, Answer> for Solution {
         let (map, instructions) = lines.split(|s| s.is_empty()).collect_tuple().unwrap();
         let mut grid = Grid::from_lines(map, &GridObject::from_char);
 
-        let instructions = instructions
-            .iter()
-            .flat_map(|s| s.chars().map(Cardinal::from_char))
-            .collect_vec();
+        let instructions = Cardinal::parse_moves(&instructions.join("\n")).unwrap();
 
         let mut lanternfish_position = grid
             .positions()
@@ -254,28 +251,16 @@ impl Solver<'_, Answer> for Solution {
 
     fn solve_part_two(&self, lines: &[&str]) -> Answer {
         let (map, instructions) = lines.split(|s| s.is_empty()).collect_tuple().unwrap();
-        let map = map
-            .iter()
-            .map(|s| {
-                s.chars()
-                    .map(|c| match c {
-                        '#' => "##",
-                        '.' => "..",
-                        '@' => "@.",
-                        'O' => "[]",
-                        _ => panic!("Unknown character "),
-                    })
-                    .collect::<String>()
-            })
-            .collect_vec();
-        let map: Vec<&str> = map.iter().map(std::ops::Deref::deref).collect();
-
-        let mut grid = Grid::from_lines(&map, &GridObject::from_char);
+        let mut grid =
+            Grid::from_lines(map, &GridObject::from_char).scale_with(2, 1, &|o| match o {
+                GridObject::Wall => vec![GridObject::Wall, GridObject::Wall],
+                GridObject::Empty => vec![GridObject::Empty, GridObject::Empty],
+                GridObject::Lanternfish => vec![GridObject::Lanternfish, GridObject::Empty],
+                GridObject::Box => vec![GridObject::BoxLeft, GridObject::BoxRight],
+                _ => panic!("Unknown object {:?}", o),
+            });
 
-        let instructions = instructions
-            .iter()
-            .flat_map(|s| s.chars().map(Cardinal::from_char))
-            .collect_vec();
+        let instructions = Cardinal::parse_moves(&instructions.join("\n")).unwrap();
 
         let mut lanternfish_position = grid
             .positions()
@@ -1,6 +1,6 @@
 use std::collections::{HashSet, VecDeque};
 
-use aoc::{Grid, Solver};
+use aoc::{shortest_path_len, Grid, Solver};
 use itertools::Itertools;
 use log::debug;
 
@@ -31,27 +31,11 @@ impl Solver<'_, Answer> for Solution {
             *grid.mut_at((x.parse().unwrap(), y.parse().unwrap())) = GridObject::Wall(t);
         }
 
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::new();
-
-        queue.push_back(((0, 0), 0));
-        visited.insert((0, 0));
-
-        while let Some((pos, length)) = queue.pop_front() {
-            if pos == (size.0 - 1, size.1 - 1) {
-                return length.to_string();
-            }
-            let neighbors = grid.cardinal_neighbor_positions(pos);
-            for neighbor in neighbors {
-                if !visited.contains(&neighbor) && *grid.at(neighbor) == GridObject::Empty {
-                    queue.push_back((neighbor, length + 1));
-                    visited.insert(neighbor);
-                }
-            }
-
-            visited.insert(pos);
-        }
-        Answer::default()
+        shortest_path_len(&grid, (0, 0), (size.0 - 1, size.1 - 1), |pos| {
+            *grid.at(pos) == GridObject::Empty
+        })
+        .map(|length| length.to_string())
+        .unwrap_or_default()
     }
 
     fn solve_part_two(&self, lines: &[&str]) -> Answer {
@@ -80,15 +80,10 @@ fn manhattan_within_d(
 type Cheat = ((usize, usize), (usize, usize));
 
 fn cheat_savings(lines: &[&str], cheat_length: usize) -> Answer {
-    let grid = Grid::from_lines(lines, &GridObject::from_char);
-    let start = grid
-        .positions()
-        .find(|pos| matches!(grid.at(*pos), GridObject::Start))
-        .unwrap();
-    let end = grid
-        .positions()
-        .find(|pos| matches!(grid.at(*pos), GridObject::End))
-        .unwrap();
+    let parsed = Grid::from_lines_with_markers(lines, "SE", &GridObject::from_char).unwrap();
+    let start = parsed.marker('S').unwrap();
+    let end = parsed.marker('E').unwrap();
+    let grid = parsed.grid;
 
     let shortest_path_map = shortest_paths_to(&grid, end);
     let base_shortest = *shortest_path_map.get(&start).unwrap();
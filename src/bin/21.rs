@@ -1,8 +1,5 @@
 use cached::proc_macro::cached;
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    sync::OnceLock,
-};
+use std::{collections::HashMap, sync::OnceLock};
 
 use aoc::{Cardinal, Graph, Solver};
 use itertools::Itertools;
@@ -41,8 +38,7 @@ impl Button {
 fn bidirectional(graph: &mut Graph<Button, Cardinal>, from: char, to: char, cardinal: Cardinal) {
     let from = Button::from_char(from);
     let to = Button::from_char(to);
-    graph.add_edge(from, to, cardinal);
-    graph.add_edge(to, from, cardinal.opposite());
+    graph.add_edge_pair(from, to, cardinal, cardinal.opposite());
 }
 
 fn build_numeric_keypad() -> Graph<Button, Cardinal> {
@@ -86,38 +82,15 @@ fn build_path_map(graph: &Graph<Button, Cardinal>) -> HashMap<(Button, Button),
     graph
         .all_vertices()
         .cartesian_product(graph.edges.keys())
-        .fold(HashMap::new(), |mut acc, (start, end)| {
-            let mut queue = VecDeque::new();
-            queue.push_back((start, Vec::new(), HashSet::new()));
-
-            let mut paths = Vec::new();
-            let mut lowest = usize::MAX;
-
-            while let Some((node, path, mut visited)) = queue.pop_front() {
-                if node == end {
-                    if path.len() <= lowest {
-                        lowest = path.len();
-                        paths.push(path);
-                    }
-                    continue;
-                }
-
-                if visited.contains(&node) {
-                    continue;
-                }
-                visited.insert(node);
-
-                for (neighbor, direction) in graph.edges.get(node).unwrap() {
-                    let mut path = path.clone();
-                    path.push(Button::Direction(*direction));
-                    queue.push_back((neighbor, path, visited.clone()));
-                }
-            }
-
-            acc.insert((*start, *end), paths);
-
-            acc
+        .map(|(start, end)| {
+            let paths = graph
+                .all_shortest_paths(start, end, |_| 1)
+                .into_iter()
+                .map(|path| path.edges.into_iter().map(Button::Direction).collect_vec())
+                .collect_vec();
+            ((*start, *end), paths)
         })
+        .collect()
 }
 
 type PathMap = HashMap<(Button, Button), Vec<Vec<Button>>>;
@@ -1,17 +1,19 @@
-use aoc::Solver;
+use aoc::{Graph, Solver};
 use itertools::Itertools;
 use log::debug;
 use regex::Regex;
+// HashSet and friends are used by the commented out part two exploration below
+#[allow(unused_imports)]
 use std::{
     borrow::Borrow,
     collections::{HashMap, HashSet, VecDeque},
     fs::File,
-    io::{Result, Write},
+    io::Result,
 };
 
 type Answer = String;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 enum Operator {
     And,
     Or,
@@ -50,28 +52,21 @@ fn read_value(values: &HashMap<&str, bool>, name: &str) -> usize {
 }
 
 fn generate_graphviz(operations: &Operations, file_path: &str) -> std::io::Result<()> {
-    let mut file = File::create(file_path)?;
-
-    writeln!(file, "digraph Operations {{")?;
-    writeln!(file, "  rankdir=LR;")?; // Layout graph left-to-right
-    writeln!(file, "  node [shape=circle];")?;
-
+    let mut graph = Graph::new();
     for (key, ops) in operations {
         for (_, operator, result) in ops {
-            writeln!(
-                file,
-                "  \"{}\" -> \"{}\" [label=\"{:?}\"];",
-                key, result, operator
-            )?;
-            // writeln!(
-            //     file,
-            //     "  \"{}\" -> \"{}\" [label=\"{:?}\"];",
-            //     operand, result, operator
-            // )?;
+            graph.add_edge(*key, *result, *operator);
         }
     }
 
-    writeln!(file, "}}")?;
+    let mut file = File::create(file_path)?;
+    graph
+        .exporter()
+        .name("Operations")
+        .node_attributes("shape=circle")
+        .vertex_labels(|v| v.to_string())
+        .edge_labels(|operator| format!("{:?}", operator))
+        .write_dot(&mut file)?;
     Ok(())
 }
 
@@ -153,6 +148,8 @@ impl Solver<'_, Answer> for Solution {
         read_value(&values, "z").to_string()
     }
 
+    // Part two is still being worked out by hand, so the target isn't used yet
+    #[allow(unused_variables)]
     fn solve_part_two(&self, lines: &[&str]) -> Answer {
         let (start_values, operations) = parse_input(lines);
         // let values = simulate(&start_values, &operations);
@@ -9,15 +9,15 @@ const PART_TWO_SAMPLE_ANSWER: Answer = 0;
 struct Solution {}
 impl Solver<'_, Answer> for Solution {
     fn solve_part_one(&self, lines: &[&str]) -> Answer {
-        let grids = lines
-            .split(|line| line.is_empty())
-            .map(|chunk| aoc::Grid::from_lines(chunk, &|c| c));
+        let grids = aoc::Grid::from_sections(lines, &|c| c).unwrap();
 
         let (keys, locks): (Vec<_>, Vec<_>) =
-            grids.partition_map(|grid| match *grid.at((0, 0)) == '#' {
-                true => Either::Left(grid),
-                false => Either::Right(grid),
-            });
+            grids
+                .into_iter()
+                .partition_map(|grid| match *grid.at((0, 0)) == '#' {
+                    true => Either::Left(grid),
+                    false => Either::Right(grid),
+                });
 
         keys.into_iter()
             .cartesian_product(locks.iter())
@@ -34,6 +34,8 @@ impl Solver<'_, Answer> for Solution {
             .count()
     }
 
+    // Day 25 has no second puzzle
+    #[allow(unused_variables)]
     fn solve_part_two(&self, lines: &[&str]) -> Answer {
         Answer::default()
     }
@@ -1,7 +1,5 @@
+use aoc::{BitGrid, Grid, Simulation, SimulationEnd, Solver};
 use core::panic;
-use std::collections::HashSet;
-
-use aoc::{Grid, Solver};
 
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Copy)]
 enum Direction {
@@ -54,15 +52,15 @@ impl GridObject {
     }
 }
 
-fn get_visited(grid: &Grid<GridObject>) -> HashSet<(isize, isize)> {
+fn get_visited(grid: &Grid<GridObject>) -> BitGrid {
     let guard_position = grid
         .positions()
         .find(|pos| *grid.at(*pos) == GridObject::Guard(Direction::Up))
         .unwrap();
+    let mut visited = BitGrid::new_empty(grid.width, grid.height);
+    visited.insert(guard_position);
     let mut guard_position = (guard_position.0 as isize, guard_position.1 as isize);
     let mut direction = Direction::Up;
-    let mut visited = HashSet::<(isize, isize)>::new();
-    visited.insert(guard_position);
 
     loop {
         let new_position = direction.move_forward(guard_position);
@@ -72,7 +70,7 @@ fn get_visited(grid: &Grid<GridObject>) -> HashSet<(isize, isize)> {
                     direction = direction.turn_right();
                 }
                 GridObject::Empty | GridObject::Guard(_) => {
-                    visited.insert(new_position);
+                    visited.insert((new_position.0 as usize, new_position.1 as usize));
                     guard_position = new_position;
                 }
             }
@@ -88,7 +86,7 @@ struct Solution {}
 impl Solver<'_, usize> for Solution {
     fn solve_part_one(&self, lines: &[&str]) -> usize {
         let grid = Grid::from_lines(lines, &GridObject::from_char);
-        get_visited(&grid).len()
+        get_visited(&grid).count()
     }
 
     fn solve_part_two(&self, lines: &[&str]) -> usize {
@@ -101,41 +99,27 @@ impl Solver<'_, usize> for Solution {
         let start_direction = Direction::Up;
         let first_path_visited = get_visited(&grid);
 
-        grid.positions()
-            .filter(|pos| {
-                if !first_path_visited.contains(&(pos.0 as isize, pos.1 as isize))
-                    || grid.at(*pos) != &GridObject::Empty
-                {
-                    return false;
-                }
-                let mut grid = grid.clone();
-                *grid.mut_at(*pos) = GridObject::Wall;
-                let mut guard_position = guard_start;
-                let mut direction = start_direction;
-                let mut visited = HashSet::<((isize, isize), Direction)>::new();
-                visited.insert((guard_position, direction));
-
-                loop {
-                    let new_position = direction.move_forward(guard_position);
-                    if let Some(object) = grid.get_isize(new_position) {
-                        match object {
-                            GridObject::Wall => {
-                                direction = direction.turn_right();
-                            }
-                            GridObject::Empty | GridObject::Guard(_) => {
-                                if visited.contains(&(new_position, direction)) {
-                                    return true;
-                                }
-                                visited.insert((new_position, direction));
-                                guard_position = new_position;
-                            }
+        grid.par_filter_positions(|pos| {
+            if !first_path_visited.contains(pos) || grid.at(pos) != &GridObject::Empty {
+                return false;
+            }
+            let overlay = grid.overlay().with(pos, GridObject::Wall);
+            let mut simulation = Simulation::new(
+                &grid,
+                (guard_start, start_direction),
+                |_, (guard_position, direction)| {
+                    let new_position = direction.move_forward(*guard_position);
+                    match overlay.get_isize(new_position)? {
+                        GridObject::Wall => Some((*guard_position, direction.turn_right())),
+                        GridObject::Empty | GridObject::Guard(_) => {
+                            Some((new_position, *direction))
                         }
-                    } else {
-                        return false;
                     }
-                }
-            })
-            .count()
+                },
+            );
+            matches!(simulation.find_cycle(), SimulationEnd::Cycle(_))
+        })
+        .len()
     }
 }
 
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use aoc::{Grid, Solver};
+use aoc::{group_pairs, group_points, line_through, BoundingBox, Grid, Point, Solver};
 use itertools::Itertools;
 use log::debug;
 
@@ -60,36 +60,15 @@ impl Solver<'_, usize> for Solution {
             '.' => GridObject::Empty,
             c => GridObject::Antenna(c),
         });
-        let antennae: HashMap<char, HashSet<(usize, usize)>> =
-            grid.positions().fold(HashMap::new(), |mut map, pos| {
-                if let GridObject::Antenna(c) = grid.at(pos) {
-                    map.entry(*c).or_default().insert(pos);
-                }
-                map
-            });
+        let antennae = group_points(grid.positions().filter_map(|pos| match grid.at(pos) {
+            GridObject::Antenna(c) => Some((*c, (pos.0 as isize, pos.1 as isize))),
+            GridObject::Empty => None,
+        }));
+        let bounds = BoundingBox::from_size(grid.width, grid.height);
 
-        let antinodes = antennae
-            .iter()
-            .fold(HashSet::new(), |mut map, (_c, c_antennae)| {
-                debug!("c: {_c}, c_antennae: {c_antennae:?}");
-                c_antennae.iter().tuple_combinations().for_each(|(a, b)| {
-                    let a = (a.0 as isize, a.1 as isize);
-                    let b = (b.0 as isize, b.1 as isize);
-                    let run = b.0 - a.0;
-                    let rise = b.1 - a.1;
-                    let mut current = a;
-                    while grid.get_isize(current).is_some() {
-                        map.insert(current);
-                        current = (current.0 + run, current.1 + rise);
-                    }
-                    current = a;
-                    while grid.get_isize(current).is_some() {
-                        map.insert(current);
-                        current = (current.0 - run, current.1 - rise);
-                    }
-                });
-                map
-            });
+        let antinodes: HashSet<Point> = group_pairs(&antennae)
+            .flat_map(|(_c, a, b)| line_through(a, b, &bounds))
+            .collect();
 
         antinodes.len()
     }
@@ -0,0 +1,443 @@
+use crate::{Cardinal, Grid};
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Display;
+
+const WORD_BITS: usize = 64;
+
+// Grid of booleans packed 64 to a word. Each row starts on a fresh word so rows can be
+// shifted independently, and bits past `width` are always kept clear
+#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    words_per_row: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl BitGrid {
+    pub fn new_empty(width: usize, height: usize) -> BitGrid {
+        let words_per_row = width.div_ceil(WORD_BITS);
+        BitGrid {
+            words: vec![0; words_per_row * height],
+            words_per_row,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_grid<T: Default + Clone>(grid: &Grid<T>, is_set: &dyn Fn(&T) -> bool) -> BitGrid {
+        let mut bits = BitGrid::new_empty(grid.width, grid.height);
+        for pos in grid.positions() {
+            if is_set(grid.at(pos)) {
+                bits.set(pos, true);
+            }
+        }
+        bits
+    }
+
+    // Rejects non-rectangular input the same way Grid::try_from_lines does
+    pub fn from_lines(lines: &[&str], set_char: char) -> Result<BitGrid, String> {
+        let grid = Grid::try_from_lines(lines, &|c| c == set_char)?;
+        Ok(BitGrid::from_grid(&grid, &|set| *set))
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::new(
+            self.positions().map(|pos| self.at(pos)).collect_vec(),
+            self.width,
+            self.height,
+        )
+    }
+
+    fn word_and_mask(&self, pos: (usize, usize)) -> (usize, u64) {
+        let (x, y) = pos;
+        (y * self.words_per_row + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    fn last_word_mask(&self) -> u64 {
+        match self.width % WORD_BITS {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    pub fn at(&self, pos: (usize, usize)) -> bool {
+        assert!(
+            pos.0 < self.width && pos.1 < self.height,
+            "{:?} out of bounds",
+            pos
+        );
+        let (word, mask) = self.word_and_mask(pos);
+        self.words[word] & mask != 0
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<bool> {
+        if pos.0 < self.width && pos.1 < self.height {
+            Some(self.at(pos))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_isize(&self, pos: (isize, isize)) -> Option<bool> {
+        if pos.0 >= 0 && pos.1 >= 0 {
+            self.get((pos.0 as usize, pos.1 as usize))
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, pos: (usize, usize), value: bool) {
+        assert!(
+            pos.0 < self.width && pos.1 < self.height,
+            "{:?} out of bounds",
+            pos
+        );
+        let (word, mask) = self.word_and_mask(pos);
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    // Sets the bit, returning true if it was previously clear (like HashSet::insert)
+    pub fn insert(&mut self, pos: (usize, usize)) -> bool {
+        let was_set = self.at(pos);
+        self.set(pos, true);
+        !was_set
+    }
+
+    pub fn contains(&self, pos: (usize, usize)) -> bool {
+        self.get(pos).unwrap_or(false)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn count_row(&self, y: usize) -> usize {
+        self.row_words(y)
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        (0..self.height)
+            .cartesian_product(0..self.width)
+            .map(|(y, x)| (x, y))
+    }
+
+    // Positions of set bits, in row-major order
+    pub fn ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(idx, word)| {
+            let y = idx / self.words_per_row;
+            let x_base = (idx % self.words_per_row) * WORD_BITS;
+            let mut remaining = *word;
+            std::iter::from_fn(move || {
+                if remaining == 0 {
+                    None
+                } else {
+                    let bit = remaining.trailing_zeros() as usize;
+                    remaining &= remaining - 1;
+                    Some((x_base + bit, y))
+                }
+            })
+        })
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = bool> + '_ {
+        (0..self.width).map(move |x| self.at((x, y)))
+    }
+
+    pub fn col(&self, x: usize) -> impl Iterator<Item = bool> + '_ {
+        (0..self.height).map(move |y| self.at((x, y)))
+    }
+
+    fn row_words(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    fn assert_same_size(&self, other: &BitGrid) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "BitGrid sizes differ: {}x{} vs {}x{}",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+    }
+
+    fn combine_with(&mut self, other: &BitGrid, op: impl Fn(u64, u64) -> u64) {
+        self.assert_same_size(other);
+        for (word, other_word) in self.words.iter_mut().zip(other.words.iter()) {
+            *word = op(*word, *other_word);
+        }
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a | b);
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a & b);
+    }
+
+    pub fn difference_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a & !b);
+    }
+
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    pub fn difference(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.difference_with(other);
+        result
+    }
+
+    pub fn invert(&mut self) {
+        let last_mask = self.last_word_mask();
+        for (idx, word) in self.words.iter_mut().enumerate() {
+            *word = !*word;
+            if idx % self.words_per_row == self.words_per_row - 1 {
+                *word &= last_mask;
+            }
+        }
+    }
+
+    // Moves every bit `n` columns towards x = 0, dropping bits that fall off the edge
+    pub fn shift_left(&mut self, n: usize) {
+        let (word_shift, bit_shift) = (n / WORD_BITS, n % WORD_BITS);
+        for y in 0..self.height {
+            let row = &mut self.words[y * self.words_per_row..(y + 1) * self.words_per_row];
+            for i in 0..row.len() {
+                let src = i + word_shift;
+                let low = row.get(src).copied().unwrap_or(0);
+                let high = row.get(src + 1).copied().unwrap_or(0);
+                row[i] = if bit_shift == 0 {
+                    low
+                } else {
+                    (low >> bit_shift) | (high << (WORD_BITS - bit_shift))
+                };
+            }
+        }
+    }
+
+    // Moves every bit `n` columns away from x = 0, dropping bits past the width
+    pub fn shift_right(&mut self, n: usize) {
+        let (word_shift, bit_shift) = (n / WORD_BITS, n % WORD_BITS);
+        let last_mask = self.last_word_mask();
+        for y in 0..self.height {
+            let row = &mut self.words[y * self.words_per_row..(y + 1) * self.words_per_row];
+            for i in (0..row.len()).rev() {
+                let high = if i >= word_shift {
+                    row[i - word_shift]
+                } else {
+                    0
+                };
+                let low = if i > word_shift {
+                    row[i - word_shift - 1]
+                } else {
+                    0
+                };
+                row[i] = if bit_shift == 0 {
+                    high
+                } else {
+                    (high << bit_shift) | (low >> (WORD_BITS - bit_shift))
+                };
+            }
+            if let Some(last) = row.last_mut() {
+                *last &= last_mask;
+            }
+        }
+    }
+
+    // Moves every row `n` rows towards y = 0
+    pub fn shift_up(&mut self, n: usize) {
+        let offset = (n * self.words_per_row).min(self.words.len());
+        self.words.copy_within(offset.., 0);
+        let len = self.words.len();
+        self.words[len - offset..].fill(0);
+    }
+
+    // Moves every row `n` rows away from y = 0
+    pub fn shift_down(&mut self, n: usize) {
+        let offset = (n * self.words_per_row).min(self.words.len());
+        let len = self.words.len();
+        self.words.copy_within(..len - offset, offset);
+        self.words[..offset].fill(0);
+    }
+
+    pub fn get_neighbor_position(
+        &self,
+        pos: (usize, usize),
+        cardinal: Cardinal,
+    ) -> Option<(usize, usize)> {
+        let (dx, dy) = cardinal.delta();
+        let neighbor = (pos.0 as isize + dx, pos.1 as isize + dy);
+        self.get_isize(neighbor)
+            .map(|_| (neighbor.0 as usize, neighbor.1 as usize))
+    }
+
+    pub fn cardinal_neighbor_positions(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        Cardinal::ALL
+            .iter()
+            .filter_map(|cardinal| self.get_neighbor_position(pos, *cardinal))
+            .collect_vec()
+    }
+
+    pub fn neighbor_positions(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        let (x, y) = (pos.0 as isize, pos.1 as isize);
+        (-1..=1)
+            .cartesian_product(-1..=1)
+            .filter(|delta| *delta != (0, 0))
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|neighbor| self.get_isize(*neighbor).is_some())
+            .map(|(x, y)| (x as usize, y as usize))
+            .collect_vec()
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        for y in 0..self.height {
+            for bit in self.row(y) {
+                write!(f, "{}", if bit { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BitGrid, Grid};
+
+    type Shift = fn(&mut BitGrid, usize);
+
+    // Deterministic scattering of bits, dense enough to cover every word
+    fn pattern(width: usize, height: usize, seed: u64) -> Grid<bool> {
+        let mut state = seed;
+        let cells = (0..width * height)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                // Top four bits below 5, so about a third of the cells are set
+                (state >> 60) < 5
+            })
+            .collect();
+        Grid::new(cells, width, height)
+    }
+
+    // Cell (x, y) of the result is cell `from(x, y)` of `grid`, or clear if that is off the grid
+    fn naive_shift(grid: &Grid<bool>, from: impl Fn(isize, isize) -> (isize, isize)) -> Grid<bool> {
+        let cells = grid
+            .positions()
+            .map(|(x, y)| {
+                *grid
+                    .get_isize(from(x as isize, y as isize))
+                    .unwrap_or(&false)
+            })
+            .collect();
+        Grid::new(cells, grid.width, grid.height)
+    }
+
+    #[test]
+    fn shifts_match_a_naive_grid() {
+        for width in [63, 64, 65, 130] {
+            let grid = pattern(width, 3, width as u64);
+            for n in [0, 1, 64, width, width + 5] {
+                let shift = n as isize;
+                let cases: [(Shift, Grid<bool>); 4] = [
+                    (
+                        BitGrid::shift_left,
+                        naive_shift(&grid, |x, y| (x + shift, y)),
+                    ),
+                    (
+                        BitGrid::shift_right,
+                        naive_shift(&grid, |x, y| (x - shift, y)),
+                    ),
+                    (BitGrid::shift_up, naive_shift(&grid, |x, y| (x, y + shift))),
+                    (
+                        BitGrid::shift_down,
+                        naive_shift(&grid, |x, y| (x, y - shift)),
+                    ),
+                ];
+                for (i, (shift_fn, expected)) in cases.iter().enumerate() {
+                    let mut bits = BitGrid::from_grid(&grid, &|b| *b);
+                    shift_fn(&mut bits, n);
+                    assert_eq!(bits.to_grid(), *expected, "width {width}, shift {i} by {n}");
+                    assert_eq!(bits.count(), expected.state.iter().filter(|b| **b).count());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invert_and_set_operations() {
+        for width in [63, 64, 65, 130] {
+            let (a, b) = (pattern(width, 2, 1), pattern(width, 2, 2));
+            let (bits_a, bits_b) = (
+                BitGrid::from_grid(&a, &|v| *v),
+                BitGrid::from_grid(&b, &|v| *v),
+            );
+
+            let mut inverted = bits_a.clone();
+            inverted.invert();
+            assert_eq!(inverted.count(), width * 2 - bits_a.count());
+            inverted.invert();
+            assert_eq!(inverted, bits_a);
+
+            let naive = |op: fn(bool, bool) -> bool| {
+                let cells = a
+                    .state
+                    .iter()
+                    .zip(&b.state)
+                    .map(|(x, y)| op(*x, *y))
+                    .collect();
+                Grid::new(cells, width, 2)
+            };
+            assert_eq!(bits_a.union(&bits_b).to_grid(), naive(|x, y| x || y));
+            assert_eq!(bits_a.intersection(&bits_b).to_grid(), naive(|x, y| x && y));
+            assert_eq!(bits_a.difference(&bits_b).to_grid(), naive(|x, y| x && !y));
+
+            let ones = bits_a.ones().collect::<Vec<_>>();
+            let expected = a.positions().filter(|pos| *a.at(*pos)).collect::<Vec<_>>();
+            let mut sorted = ones.clone();
+            sorted.sort_by_key(|(x, y)| (*y, *x));
+            assert_eq!(sorted, expected);
+            assert_eq!(ones.len(), bits_a.count());
+        }
+    }
+
+    #[test]
+    fn from_lines_rejects_ragged_input() {
+        let bits = BitGrid::from_lines(&["#.", ".#"], '#').unwrap();
+        assert!(bits.at((0, 0)) && bits.at((1, 1)) && !bits.at((1, 0)));
+        assert!(BitGrid::from_lines(&["#.", ".##"], '#').is_err());
+    }
+}
@@ -0,0 +1,235 @@
+use crate::Graph;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+// Vertices numbered densely, with adjacency in both directions so that cliques and
+// other undirected notions also work on directed graphs
+pub(crate) struct Adjacency<'a, V> {
+    pub(crate) vertices: Vec<&'a V>,
+    pub(crate) neighbors: Vec<HashSet<usize>>,
+}
+
+impl<'a, V: Eq + Hash> Adjacency<'a, V> {
+    pub(crate) fn new<E>(graph: &'a Graph<V, E>) -> Adjacency<'a, V> {
+        let vertices = graph
+            .edges
+            .iter()
+            .flat_map(|(from, edges)| std::iter::once(from).chain(edges.keys()))
+            .unique()
+            .collect_vec();
+        let index: HashMap<&V, usize> = vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut neighbors = vec![HashSet::new(); vertices.len()];
+        for (from, edges) in &graph.edges {
+            for to in edges.keys() {
+                let (a, b) = (index[from], index[to]);
+                if a != b {
+                    neighbors[a].insert(b);
+                    neighbors[b].insert(a);
+                }
+            }
+        }
+        Adjacency {
+            vertices,
+            neighbors,
+        }
+    }
+
+    fn resolve(&self, clique: &[usize]) -> Vec<V>
+    where
+        V: Clone,
+    {
+        clique.iter().map(|i| self.vertices[*i].clone()).collect()
+    }
+
+    // Bron–Kerbosch, pivoting on the vertex that leaves the fewest candidates to branch on
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: HashSet<usize>,
+        mut excluded: HashSet<usize>,
+        found: &mut dyn FnMut(&[usize]),
+    ) {
+        if candidates.is_empty() && excluded.is_empty() {
+            found(clique);
+            return;
+        }
+        let pivot = candidates
+            .union(&excluded)
+            .max_by_key(|v| self.neighbors[**v].intersection(&candidates).count())
+            .copied()
+            .unwrap();
+        let branches = candidates
+            .difference(&self.neighbors[pivot])
+            .copied()
+            .collect_vec();
+        for v in branches {
+            let neighbors = &self.neighbors[v];
+            clique.push(v);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbors).copied().collect(),
+                excluded.intersection(neighbors).copied().collect(),
+                found,
+            );
+            clique.pop();
+            candidates.remove(&v);
+            excluded.insert(v);
+        }
+    }
+
+    // Each clique is built in increasing vertex order, so it is only found once
+    fn extend_clique(
+        &self,
+        k: usize,
+        clique: &mut Vec<usize>,
+        candidates: &[usize],
+        found: &mut dyn FnMut(&[usize]),
+    ) {
+        if clique.len() == k {
+            found(clique);
+            return;
+        }
+        for (i, v) in candidates.iter().enumerate() {
+            let rest = candidates[i + 1..]
+                .iter()
+                .copied()
+                .filter(|u| self.neighbors[*v].contains(u))
+                .collect_vec();
+            if clique.len() + 1 + rest.len() < k {
+                continue;
+            }
+            clique.push(*v);
+            self.extend_clique(k, clique, &rest, found);
+            clique.pop();
+        }
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    pub fn maximal_cliques(&self) -> Vec<Vec<V>> {
+        self.maximal_cliques_where(|_| true)
+    }
+
+    // Only the maximal cliques that `keep` accepts
+    pub fn maximal_cliques_where(&self, keep: impl Fn(&[V]) -> bool) -> Vec<Vec<V>> {
+        let adjacency = Adjacency::new(self);
+        let mut cliques = Vec::new();
+        adjacency.bron_kerbosch(
+            &mut Vec::new(),
+            (0..adjacency.vertices.len()).collect(),
+            HashSet::new(),
+            &mut |clique| {
+                let clique = adjacency.resolve(clique);
+                if keep(&clique) {
+                    cliques.push(clique);
+                }
+            },
+        );
+        cliques
+    }
+
+    // Largest clique, or an empty one for an empty graph
+    pub fn maximum_clique(&self) -> Vec<V> {
+        self.maximal_cliques()
+            .into_iter()
+            .max_by_key(|clique| clique.len())
+            .unwrap_or_default()
+    }
+
+    pub fn triangles(&self) -> Vec<Vec<V>> {
+        self.k_cliques(3)
+    }
+
+    pub fn k_cliques(&self, k: usize) -> Vec<Vec<V>> {
+        self.k_cliques_where(k, |_| true)
+    }
+
+    // Every set of `k` mutually adjacent vertices that `keep` accepts. With k = 1 that is
+    // every vertex on its own, and k = 0 finds nothing
+    pub fn k_cliques_where(&self, k: usize, keep: impl Fn(&[V]) -> bool) -> Vec<Vec<V>> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let adjacency = Adjacency::new(self);
+        let all = (0..adjacency.vertices.len()).collect_vec();
+        let mut cliques = Vec::new();
+        adjacency.extend_clique(k, &mut Vec::new(), &all, &mut |clique| {
+            let clique = adjacency.resolve(clique);
+            if keep(&clique) {
+                cliques.push(clique);
+            }
+        });
+        cliques
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Graph;
+
+    fn sample() -> Graph<&'static str, ()> {
+        let mut graph = Graph::new_undirected();
+        for line in include_str!("../samples/23.txt").lines() {
+            let (a, b) = line.split_once('-').unwrap();
+            graph.add_edge(a, b, ());
+        }
+        graph
+    }
+
+    fn sorted(cliques: Vec<Vec<&str>>) -> Vec<Vec<&str>> {
+        let mut cliques = cliques
+            .into_iter()
+            .map(|mut clique| {
+                clique.sort();
+                clique
+            })
+            .collect::<Vec<_>>();
+        cliques.sort();
+        cliques
+    }
+
+    #[test]
+    fn triangles_of_the_sample() {
+        let graph = sample();
+        let triangles = sorted(graph.triangles());
+        assert_eq!(triangles.len(), 12);
+        assert!(triangles.contains(&vec!["co", "de", "ta"]));
+        let with_t = graph.k_cliques_where(3, |c| c.iter().any(|v| v.starts_with('t')));
+        assert_eq!(with_t.len(), 7);
+    }
+
+    #[test]
+    fn maximum_clique_of_the_sample() {
+        let graph = sample();
+        let mut clique = graph.maximum_clique();
+        clique.sort();
+        assert_eq!(clique, vec!["co", "de", "ka", "ta"]);
+        let maximal = sorted(graph.maximal_cliques());
+        assert!(maximal.contains(&clique));
+        // Every maximal clique is a clique that no other vertex could join
+        for clique in &maximal {
+            for (i, a) in clique.iter().enumerate() {
+                for b in &clique[i + 1..] {
+                    assert!(graph.contains_edge(a, b));
+                }
+            }
+            assert!(!graph.all_vertices().any(|v| !clique.contains(v)
+                && clique.iter().all(|member| graph.contains_edge(v, member))));
+        }
+        assert_eq!(sorted(graph.k_cliques(4)), vec![clique]);
+    }
+
+    #[test]
+    fn trivial_clique_sizes() {
+        let graph = sample();
+        assert!(graph.k_cliques(0).is_empty());
+        assert_eq!(graph.k_cliques(1).len(), graph.vertex_count());
+        assert_eq!(graph.k_cliques(2).len(), graph.edge_count());
+    }
+}
@@ -0,0 +1,448 @@
+use crate::graph_cliques::Adjacency;
+use crate::Graph;
+use itertools::Itertools;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Add;
+
+// Each strongly connected component collapsed into a single vertex, numbered by its index
+// in `components`. The result is always acyclic
+#[derive(Debug)]
+pub struct Condensation<V> {
+    pub components: Vec<Vec<V>>,
+    pub component_of: HashMap<V, usize>,
+    pub dag: Graph<usize, ()>,
+}
+
+// A partition of the vertices into `side` and `rest`, and the total weight of edges between them
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Cut<V, W> {
+    pub weight: W,
+    pub side: Vec<V>,
+    pub rest: Vec<V>,
+}
+
+struct Tarjan<'a, V> {
+    index: HashMap<&'a V, usize>,
+    low_link: HashMap<&'a V, usize>,
+    stack: Vec<&'a V>,
+    on_stack: HashSet<&'a V>,
+    components: Vec<Vec<V>>,
+}
+
+impl<'a, V: Eq + Hash> Tarjan<'a, V> {
+    fn discover(&mut self, vertex: &'a V) {
+        let index = self.index.len();
+        self.index.insert(vertex, index);
+        self.low_link.insert(vertex, index);
+        self.stack.push(vertex);
+        self.on_stack.insert(vertex);
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    // Tarjan's algorithm. Components come out in reverse topological order: no edge leads
+    // from a component to one listed after it
+    pub fn strongly_connected_components(&self) -> Vec<Vec<V>> {
+        let mut tarjan = Tarjan {
+            index: HashMap::new(),
+            low_link: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        };
+        for vertex in self.vertex_set() {
+            if !tarjan.index.contains_key(vertex) {
+                self.tarjan_visit(vertex, &mut tarjan);
+            }
+        }
+        tarjan.components
+    }
+
+    fn tarjan_visit<'a>(&'a self, root: &'a V, tarjan: &mut Tarjan<'a, V>) {
+        tarjan.discover(root);
+        // (vertex, remaining neighbors) instead of recursion, for long chains
+        let mut frames = vec![(root, self.neighbors(root).collect_vec())];
+        while let Some((vertex, remaining)) = frames.last_mut() {
+            let vertex = *vertex;
+            if let Some(neighbor) = remaining.pop() {
+                if !tarjan.index.contains_key(neighbor) {
+                    tarjan.discover(neighbor);
+                    frames.push((neighbor, self.neighbors(neighbor).collect_vec()));
+                } else if tarjan.on_stack.contains(neighbor) {
+                    let low = tarjan.low_link[vertex].min(tarjan.index[neighbor]);
+                    tarjan.low_link.insert(vertex, low);
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some((parent, _)) = frames.last() {
+                let low = tarjan.low_link[*parent].min(tarjan.low_link[vertex]);
+                tarjan.low_link.insert(*parent, low);
+            }
+            if tarjan.low_link[vertex] == tarjan.index[vertex] {
+                let mut component = Vec::new();
+                while let Some(member) = tarjan.stack.pop() {
+                    tarjan.on_stack.remove(member);
+                    component.push(member.clone());
+                    if member == vertex {
+                        break;
+                    }
+                }
+                tarjan.components.push(component);
+            }
+        }
+    }
+
+    pub fn condensation(&self) -> Condensation<V> {
+        let components = self.strongly_connected_components();
+        let component_of: HashMap<V, usize> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(i, component)| component.iter().map(move |v| (v.clone(), i)))
+            .collect();
+        let mut dag = Graph::new();
+        for i in 0..components.len() {
+            dag.add_vertex(i);
+        }
+        for (from, edges) in &self.edges {
+            for to in edges.keys() {
+                let (a, b) = (component_of[from], component_of[to]);
+                if a != b {
+                    dag.add_edge(a, b, ());
+                }
+            }
+        }
+        Condensation {
+            components,
+            component_of,
+            dag,
+        }
+    }
+
+    // Edges whose removal disconnects the graph, ignoring edge direction
+    pub fn bridges(&self) -> Vec<(V, V)> {
+        let adjacency = Adjacency::new(self);
+        let mut bridges = Vec::new();
+        self.low_points(&adjacency, |v, child, low, discovered| {
+            if low[child] > discovered[v] {
+                bridges.push((
+                    adjacency.vertices[v].clone(),
+                    adjacency.vertices[child].clone(),
+                ));
+            }
+        });
+        bridges
+    }
+
+    // Vertices whose removal disconnects the graph, ignoring edge direction
+    pub fn articulation_points(&self) -> Vec<V> {
+        let adjacency = Adjacency::new(self);
+        let mut points = HashSet::new();
+        let mut root_children = HashMap::new();
+        self.low_points(&adjacency, |v, child, low, discovered| {
+            if discovered[v] == 0 {
+                *root_children.entry(v).or_insert(0) += 1;
+            } else if low[child] >= discovered[v] {
+                points.insert(v);
+            }
+        });
+        points.extend(
+            root_children
+                .into_iter()
+                .filter(|(_, children)| *children > 1)
+                .map(|(root, _)| root),
+        );
+        points
+            .into_iter()
+            .sorted()
+            .map(|v| adjacency.vertices[v].clone())
+            .collect()
+    }
+
+    // Depth first search numbering every tree, calling `visit_child(v, child, low, discovered)`
+    // once the subtree under `child` is finished. `discovered` is 0 exactly for tree roots
+    fn low_points(
+        &self,
+        adjacency: &Adjacency<V>,
+        mut visit_child: impl FnMut(usize, usize, &[usize], &[usize]),
+    ) {
+        let count = adjacency.vertices.len();
+        let mut discovered = vec![usize::MAX; count];
+        let mut low = vec![usize::MAX; count];
+        for root in 0..count {
+            if discovered[root] != usize::MAX {
+                continue;
+            }
+            let mut time = 0;
+            discovered[root] = time;
+            low[root] = time;
+            // (vertex, parent, remaining neighbors) instead of recursion, for long corridors
+            let mut stack = vec![(
+                root,
+                usize::MAX,
+                adjacency.neighbors[root].iter().copied().collect_vec(),
+            )];
+            while let Some((v, parent, remaining)) = stack.last_mut() {
+                let (v, parent) = (*v, *parent);
+                if let Some(next) = remaining.pop() {
+                    if next == parent {
+                        continue;
+                    }
+                    if discovered[next] == usize::MAX {
+                        time += 1;
+                        discovered[next] = time;
+                        low[next] = time;
+                        let neighbors = adjacency.neighbors[next].iter().copied().collect_vec();
+                        stack.push((next, v, neighbors));
+                    } else {
+                        low[v] = low[v].min(discovered[next]);
+                    }
+                } else {
+                    stack.pop();
+                    if parent != usize::MAX {
+                        low[parent] = low[parent].min(low[v]);
+                        visit_child(parent, v, &low, &discovered);
+                    }
+                }
+            }
+        }
+    }
+
+    // Stoer–Wagner global minimum cut, ignoring edge direction. None with fewer than two vertices
+    pub fn min_cut<W>(&self, weight: impl Fn(&E) -> W) -> Option<Cut<V, W>>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        let adjacency = Adjacency::new(self);
+        let count = adjacency.vertices.len();
+        if count < 2 {
+            return None;
+        }
+        let index: HashMap<&V, usize> = adjacency
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (*v, i))
+            .collect();
+        let mut weights: Vec<HashMap<usize, W>> = vec![HashMap::new(); count];
+        for (from, edges) in &self.edges {
+            for (to, edge) in edges {
+                let (a, b) = (index[from], index[to]);
+                if a == b {
+                    continue;
+                }
+                let w = weight(edge);
+                if self.undirected {
+                    // Stored in both directions already
+                    weights[a].insert(b, w);
+                } else {
+                    for (x, y) in [(a, b), (b, a)] {
+                        let total = weights[x].entry(y).or_default();
+                        *total = *total + w;
+                    }
+                }
+            }
+        }
+
+        let mut groups = (0..count).map(|i| vec![i]).collect_vec();
+        let mut active = (0..count).collect::<HashSet<_>>();
+        let mut best: Option<(W, Vec<usize>)> = None;
+        while active.len() > 1 {
+            // Maximum adjacency ordering: repeatedly add the vertex most tightly connected
+            // to everything added so far
+            let mut connection: HashMap<usize, W> =
+                active.iter().map(|v| (*v, W::default())).collect();
+            let mut heap = active
+                .iter()
+                .map(|v| (W::default(), *v))
+                .collect::<BinaryHeap<_>>();
+            let mut added = HashSet::new();
+            let mut order = Vec::new();
+            while let Some((w, v)) = heap.pop() {
+                if added.contains(&v) || connection[&v] != w {
+                    continue;
+                }
+                added.insert(v);
+                order.push(v);
+                for (n, edge_weight) in &weights[v] {
+                    if !added.contains(n) {
+                        let total = connection[n] + *edge_weight;
+                        connection.insert(*n, total);
+                        heap.push((total, *n));
+                    }
+                }
+            }
+            let (s, t) = (order[order.len() - 2], order[order.len() - 1]);
+            let cut_of_phase = connection[&t];
+            if best.as_ref().is_none_or(|(w, _)| cut_of_phase < *w) {
+                best = Some((cut_of_phase, groups[t].clone()));
+            }
+
+            // Merge t into s
+            let merged = std::mem::take(&mut weights[t]);
+            for (n, w) in merged {
+                weights[n].remove(&t);
+                if n != s {
+                    let total = weights[s].entry(n).or_default();
+                    *total = *total + w;
+                    let total = weights[n].entry(s).or_default();
+                    *total = *total + w;
+                }
+            }
+            let members = std::mem::take(&mut groups[t]);
+            groups[s].extend(members);
+            active.remove(&t);
+        }
+
+        let (weight, side) = best?;
+        let side_set = side.iter().copied().collect::<HashSet<_>>();
+        Some(Cut {
+            weight,
+            side: side
+                .iter()
+                .map(|i| adjacency.vertices[*i].clone())
+                .collect(),
+            rest: (0..count)
+                .filter(|i| !side_set.contains(i))
+                .map(|i| adjacency.vertices[i].clone())
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Graph;
+
+    fn chain(length: usize) -> Graph<usize, ()> {
+        let mut graph = Graph::new();
+        for i in 1..length {
+            graph.add_edge(i - 1, i, ());
+        }
+        graph
+    }
+
+    #[test]
+    fn cycles_form_components() {
+        let mut graph = chain(5);
+        graph.add_edge(3, 1, ());
+        let mut components = graph
+            .strongly_connected_components()
+            .into_iter()
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .collect::<Vec<_>>();
+        components.sort();
+        assert_eq!(components, vec![vec![0], vec![1, 2, 3], vec![4]]);
+    }
+
+    // Triangles a-b-c and d-e-f joined by the single edge c-d
+    fn bowtie(undirected: bool) -> Graph<&'static str, u32> {
+        let mut graph = if undirected {
+            Graph::new_undirected()
+        } else {
+            Graph::new()
+        };
+        for (from, to) in [
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "f"),
+            ("f", "d"),
+        ] {
+            graph.add_edge(from, to, 1);
+        }
+        graph
+    }
+
+    #[test]
+    fn bridges_and_articulation_points() {
+        for undirected in [true, false] {
+            let graph = bowtie(undirected);
+            let bridges = graph
+                .bridges()
+                .into_iter()
+                .map(|(a, b)| if a < b { (a, b) } else { (b, a) })
+                .collect::<Vec<_>>();
+            assert_eq!(bridges, vec![("c", "d")]);
+            assert_eq!(sorted(graph.articulation_points()), vec!["c", "d"]);
+        }
+    }
+
+    // Two complete clusters of four with heavy edges, joined by one light edge
+    fn clusters(undirected: bool) -> Graph<(char, u32), u32> {
+        let mut graph = if undirected {
+            Graph::new_undirected()
+        } else {
+            Graph::new()
+        };
+        for cluster in ['a', 'b'] {
+            for i in 0..4 {
+                for j in (i + 1)..4 {
+                    graph.add_edge((cluster, i), (cluster, j), 5);
+                }
+            }
+        }
+        graph.add_edge(('a', 0), ('b', 0), 2);
+        graph
+    }
+
+    fn sorted<T: Ord>(mut items: Vec<T>) -> Vec<T> {
+        items.sort();
+        items
+    }
+
+    #[test]
+    fn min_cut_separates_the_clusters() {
+        let cut = clusters(true).min_cut(|w| *w).unwrap();
+        assert_eq!(cut.weight, 2);
+        let side = sorted(cut.side);
+        let rest = sorted(cut.rest);
+        let a = (0..4).map(|i| ('a', i)).collect::<Vec<_>>();
+        let b = (0..4).map(|i| ('b', i)).collect::<Vec<_>>();
+        assert!((side == a && rest == b) || (side == b && rest == a));
+    }
+
+    #[test]
+    fn min_cut_of_directed_input_adds_both_directions() {
+        let mut graph = clusters(false);
+        let cut = graph.min_cut(|w| *w).unwrap();
+        assert_eq!(cut.weight, 2);
+        assert_eq!(cut.side.len(), 4);
+
+        graph.add_edge(('b', 0), ('a', 0), 1);
+        let cut = graph.min_cut(|w| *w).unwrap();
+        assert_eq!(cut.weight, 3);
+        let side = sorted(cut.side);
+        assert!(side.iter().all(|v| v.0 == side[0].0));
+        assert_eq!(side.len(), 4);
+    }
+
+    #[test]
+    fn long_chain_does_not_overflow_the_stack() {
+        let length = 20_000;
+        // Run with a small stack like a spawned solution thread, rather than whatever the
+        // test harness was given
+        std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(move || {
+                let graph = chain(length);
+                assert_eq!(graph.strongly_connected_components().len(), length);
+                assert_eq!(graph.condensation().dag.edge_count(), length - 1);
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+}
@@ -0,0 +1,231 @@
+use crate::Graph;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+// Maps vertices to dense ids 0..len, in the order they were first seen
+#[derive(Debug, Clone)]
+pub struct Interner<V> {
+    values: Vec<V>,
+    ids: HashMap<V, u32>,
+}
+
+impl<V: Eq + Hash + Clone> Interner<V> {
+    pub fn new() -> Interner<V> {
+        Interner {
+            values: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    pub fn intern(&mut self, value: &V) -> u32 {
+        if let Some(id) = self.ids.get(value) {
+            return *id;
+        }
+        let id = self.values.len() as u32;
+        self.values.push(value.clone());
+        self.ids.insert(value.clone(), id);
+        id
+    }
+
+    pub fn id(&self, value: &V) -> Option<u32> {
+        self.ids.get(value).copied()
+    }
+
+    pub fn value(&self, id: u32) -> &V {
+        &self.values[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.values.iter()
+    }
+}
+
+impl<V: Eq + Hash + Clone> Default for Interner<V> {
+    fn default() -> Self {
+        Interner::new()
+    }
+}
+
+// Immutable compressed sparse row copy of a Graph. The edges leaving vertex `id` are
+// `targets[offsets[id]..offsets[id + 1]]`, with payloads at the same indices in `payloads`
+#[derive(Debug, Clone)]
+pub struct DenseGraph<V, E> {
+    pub interner: Interner<V>,
+    pub undirected: bool,
+    offsets: Vec<usize>,
+    targets: Vec<u32>,
+    payloads: Vec<E>,
+}
+
+impl<V, E> DenseGraph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    pub fn from_graph(graph: &Graph<V, E>) -> DenseGraph<V, E> {
+        let mut interner = Interner::new();
+        for (from, edges) in &graph.edges {
+            interner.intern(from);
+            for to in edges.keys() {
+                interner.intern(to);
+            }
+        }
+        let mut offsets = vec![0];
+        let mut targets = Vec::new();
+        let mut payloads = Vec::new();
+        for from in interner.values() {
+            for (to, edge) in graph.edges.get(from).into_iter().flatten() {
+                targets.push(interner.id(to).unwrap());
+                payloads.push(edge.clone());
+            }
+            offsets.push(targets.len());
+        }
+        DenseGraph {
+            interner,
+            undirected: graph.undirected,
+            offsets,
+            targets,
+            payloads,
+        }
+    }
+
+    pub fn to_graph(&self) -> Graph<V, E> {
+        let mut graph = Graph {
+            edges: HashMap::new(),
+            undirected: self.undirected,
+        };
+        for from in 0..self.vertex_count() as u32 {
+            let edges = self
+                .edges_from(from)
+                .map(|(to, edge)| (self.interner.value(to).clone(), edge.clone()))
+                .collect();
+            graph.edges.insert(self.interner.value(from).clone(), edges);
+        }
+        graph
+    }
+
+    pub fn id(&self, vertex: &V) -> Option<u32> {
+        self.interner.id(vertex)
+    }
+
+    pub fn value(&self, id: u32) -> &V {
+        self.interner.value(id)
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.interner.len()
+    }
+
+    // Stored edges, so undirected edges count twice
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn neighbors(&self, id: u32) -> &[u32] {
+        let id = id as usize;
+        &self.targets[self.offsets[id]..self.offsets[id + 1]]
+    }
+
+    pub fn edges_from(&self, id: u32) -> impl Iterator<Item = (u32, &E)> {
+        let range = self.offsets[id as usize]..self.offsets[id as usize + 1];
+        self.targets[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.payloads[range].iter())
+    }
+
+    pub fn edge(&self, from: u32, to: u32) -> Option<&E> {
+        self.edges_from(from)
+            .find(|(target, _)| *target == to)
+            .map(|(_, edge)| edge)
+    }
+
+    pub fn out_degree(&self, id: u32) -> usize {
+        self.neighbors(id).len()
+    }
+
+    // Hop count from `start` to every vertex, indexed by id
+    pub fn bfs_distances(&self, start: u32) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.vertex_count()];
+        let mut queue = VecDeque::new();
+        distances[start as usize] = Some(0);
+        queue.push_back(start);
+        while let Some(vertex) = queue.pop_front() {
+            let distance = distances[vertex as usize].unwrap();
+            for neighbor in self.neighbors(vertex) {
+                if distances[*neighbor as usize].is_none() {
+                    distances[*neighbor as usize] = Some(distance + 1);
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+        distances
+    }
+
+    // Same as Graph::bfs, without cloning vertices along the way
+    pub fn bfs(&self, start: &V, end: &V) -> Option<usize> {
+        let (start, end) = (self.id(start)?, self.id(end)?);
+        self.bfs_distances(start)[end as usize]
+    }
+}
+
+impl<V, E> From<&Graph<V, E>> for DenseGraph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    fn from(graph: &Graph<V, E>) -> Self {
+        DenseGraph::from_graph(graph)
+    }
+}
+
+impl<V, E> From<&DenseGraph<V, E>> for Graph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    fn from(dense: &DenseGraph<V, E>) -> Self {
+        dense.to_graph()
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    pub fn to_dense(&self) -> DenseGraph<V, E> {
+        DenseGraph::from_graph(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Graph;
+
+    #[test]
+    fn dense_bfs_matches_graph_bfs() {
+        let mut graph = Graph::new();
+        for (from, to) in [("a", "b"), ("b", "c"), ("a", "c"), ("c", "d"), ("e", "a")] {
+            graph.add_edge(from, to, ());
+        }
+        let dense = graph.to_dense();
+        let start = dense.id(&"a").unwrap();
+        let distances = graph.all_distances(&"a");
+        for (id, distance) in dense.bfs_distances(start).into_iter().enumerate() {
+            assert_eq!(distance, distances.get(dense.value(id as u32)).copied());
+        }
+        assert_eq!(dense.bfs(&"a", &"d"), graph.bfs("a", "d"));
+        assert_eq!(dense.bfs(&"d", &"a"), None);
+        assert_eq!(dense.to_graph().edges, graph.edges);
+    }
+}
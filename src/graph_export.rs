@@ -0,0 +1,526 @@
+use crate::Graph;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::io;
+use std::io::Write;
+
+type Labeler<'a, T> = Box<dyn Fn(&T) -> String + 'a>;
+type Clusterer<'a, V> = Box<dyn Fn(&V) -> Option<String> + 'a>;
+
+// Writes a graph out for Graphviz, Mermaid or GraphML viewers. Vertices are written sorted
+// by label, then by Debug representation, so that the output is stable between runs
+pub struct GraphExporter<'a, V, E> {
+    graph: &'a Graph<V, E>,
+    name: String,
+    node_attributes: Option<String>,
+    vertex_labels: Labeler<'a, V>,
+    edge_labels: Option<Labeler<'a, E>>,
+    highlighted_vertices: HashSet<V>,
+    highlighted_edges: HashSet<(V, V)>,
+    clusters: Option<Clusterer<'a, V>>,
+}
+
+// An edge as written out, between vertex ids numbered in output order
+struct ExportedEdge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+    highlighted: bool,
+}
+
+impl<'a, V, E> GraphExporter<'a, V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    pub fn new(graph: &'a Graph<V, E>) -> GraphExporter<'a, V, E> {
+        GraphExporter {
+            graph,
+            name: "G".to_string(),
+            node_attributes: None,
+            vertex_labels: Box::new(|v| format!("{:?}", v)),
+            edge_labels: None,
+            highlighted_vertices: HashSet::new(),
+            highlighted_edges: HashSet::new(),
+            clusters: None,
+        }
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    // Default attributes for every DOT node, e.g. "shape=circle"
+    pub fn node_attributes(mut self, attributes: &str) -> Self {
+        self.node_attributes = Some(attributes.to_string());
+        self
+    }
+
+    // Defaults to the Debug representation
+    pub fn vertex_labels(mut self, labeler: impl Fn(&V) -> String + 'a) -> Self {
+        self.vertex_labels = Box::new(labeler);
+        self
+    }
+
+    pub fn edge_labels(mut self, labeler: impl Fn(&E) -> String + 'a) -> Self {
+        self.edge_labels = Some(Box::new(labeler));
+        self
+    }
+
+    pub fn highlight_vertices(mut self, vertices: impl IntoIterator<Item = V>) -> Self {
+        self.highlighted_vertices.extend(vertices);
+        self
+    }
+
+    // In an undirected graph either orientation of the pair highlights the edge
+    pub fn highlight_edges(mut self, edges: impl IntoIterator<Item = (V, V)>) -> Self {
+        self.highlighted_edges.extend(edges);
+        self
+    }
+
+    // Groups vertices under a shared name, or leaves them ungrouped with None
+    pub fn clusters(mut self, clusterer: impl Fn(&V) -> Option<String> + 'a) -> Self {
+        self.clusters = Some(Box::new(clusterer));
+        self
+    }
+
+    fn vertices(&self) -> Vec<(&'a V, String)> {
+        self.graph
+            .vertex_set()
+            .into_iter()
+            .map(|v| ((self.vertex_labels)(v), format!("{:?}", v), v))
+            .sorted_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)))
+            .map(|(label, _, v)| (v, label))
+            .collect()
+    }
+
+    fn edges(&self, ids: &HashMap<&V, usize>) -> Vec<ExportedEdge> {
+        let mut edges = self
+            .graph
+            .edges
+            .iter()
+            .flat_map(|(from, edges)| edges.iter().map(move |(to, edge)| (from, to, edge)))
+            .filter(|(from, to, _)| !self.graph.undirected || ids[from] <= ids[to])
+            .map(|(from, to, edge)| {
+                let highlighted = self.highlighted_edges.contains(&(from.clone(), to.clone()))
+                    || (self.graph.undirected
+                        && self.highlighted_edges.contains(&(to.clone(), from.clone())));
+                ExportedEdge {
+                    from: ids[from],
+                    to: ids[to],
+                    label: self.edge_labels.as_ref().map(|labeler| labeler(edge)),
+                    highlighted,
+                }
+            })
+            .collect_vec();
+        edges.sort_by_key(|edge| (edge.from, edge.to));
+        edges
+    }
+
+    // Vertex ids grouped by cluster name, with unclustered vertices under None
+    fn clustered(&self, vertices: &[(&V, String)]) -> Vec<(Option<String>, Vec<usize>)> {
+        let mut groups: Vec<(Option<String>, Vec<usize>)> = Vec::new();
+        for (id, (vertex, _)) in vertices.iter().enumerate() {
+            let cluster = self
+                .clusters
+                .as_ref()
+                .and_then(|clusterer| clusterer(vertex));
+            match groups.iter_mut().find(|(name, _)| *name == cluster) {
+                Some((_, ids)) => ids.push(id),
+                None => groups.push((cluster, vec![id])),
+            }
+        }
+        groups
+    }
+
+    fn prepare(&self) -> (Vec<(&'a V, String)>, Vec<ExportedEdge>) {
+        let vertices = self.vertices();
+        let ids: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(id, (v, _))| (*v, id))
+            .collect();
+        let edges = self.edges(&ids);
+        (vertices, edges)
+    }
+
+    pub fn write_dot(&self, out: &mut impl Write) -> io::Result<()> {
+        let (vertices, edges) = self.prepare();
+        let (keyword, arrow) = if self.graph.undirected {
+            ("graph", "--")
+        } else {
+            ("digraph", "->")
+        };
+        writeln!(out, "{} {} {{", keyword, dot_quote(&self.name))?;
+        writeln!(out, "  rankdir=LR;")?;
+        if let Some(attributes) = &self.node_attributes {
+            writeln!(out, "  node [{}];", attributes)?;
+        }
+        for (i, (cluster, ids)) in self.clustered(&vertices).into_iter().enumerate() {
+            let indent = if let Some(name) = &cluster {
+                writeln!(out, "  subgraph cluster_{} {{", i)?;
+                writeln!(out, "    label={};", dot_quote(name))?;
+                "    "
+            } else {
+                "  "
+            };
+            for id in ids {
+                let (vertex, label) = &vertices[id];
+                let style = if self.highlighted_vertices.contains(*vertex) {
+                    ", color=red, style=filled, fillcolor=mistyrose"
+                } else {
+                    ""
+                };
+                writeln!(
+                    out,
+                    "{}n{} [label={}{}];",
+                    indent,
+                    id,
+                    dot_quote(label),
+                    style
+                )?;
+            }
+            if cluster.is_some() {
+                writeln!(out, "  }}")?;
+            }
+        }
+        for edge in edges {
+            let mut attributes = Vec::new();
+            if let Some(label) = &edge.label {
+                attributes.push(format!("label={}", dot_quote(label)));
+            }
+            if edge.highlighted {
+                attributes.push("color=red, penwidth=2".to_string());
+            }
+            let attributes = if attributes.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attributes.join(", "))
+            };
+            writeln!(
+                out,
+                "  n{} {} n{}{};",
+                edge.from, arrow, edge.to, attributes
+            )?;
+        }
+        writeln!(out, "}}")
+    }
+
+    pub fn write_mermaid(&self, out: &mut impl Write) -> io::Result<()> {
+        let (vertices, edges) = self.prepare();
+        writeln!(out, "flowchart LR")?;
+        for (i, (cluster, ids)) in self.clustered(&vertices).into_iter().enumerate() {
+            let indent = if let Some(name) = &cluster {
+                writeln!(out, "  subgraph cluster_{} [{}]", i, mermaid_quote(name))?;
+                "    "
+            } else {
+                "  "
+            };
+            for id in ids {
+                writeln!(out, "{}n{}[{}]", indent, id, mermaid_quote(&vertices[id].1))?;
+            }
+            if cluster.is_some() {
+                writeln!(out, "  end")?;
+            }
+        }
+        let link = if self.graph.undirected { "---" } else { "-->" };
+        for edge in &edges {
+            match &edge.label {
+                Some(label) => writeln!(
+                    out,
+                    "  n{} {}|{}| n{}",
+                    edge.from,
+                    link,
+                    mermaid_quote(label),
+                    edge.to
+                )?,
+                None => writeln!(out, "  n{} {} n{}", edge.from, link, edge.to)?,
+            }
+        }
+        for (id, (vertex, _)) in vertices.iter().enumerate() {
+            if self.highlighted_vertices.contains(*vertex) {
+                writeln!(out, "  style n{} fill:#fdd,stroke:#f00", id)?;
+            }
+        }
+        // Mermaid addresses links by the order they were declared in
+        for (i, edge) in edges.iter().enumerate() {
+            if edge.highlighted {
+                writeln!(out, "  linkStyle {} stroke:#f00,stroke-width:2px", i)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write_graphml(&self, out: &mut impl Write) -> io::Result<()> {
+        let (vertices, edges) = self.prepare();
+        let clusters = self
+            .clustered(&vertices)
+            .into_iter()
+            .flat_map(|(cluster, ids)| ids.into_iter().map(move |id| (id, cluster.clone())))
+            .collect::<HashMap<_, _>>();
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            out,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+        )?;
+        writeln!(
+            out,
+            r#"  <key id="label" for="all" attr.name="label" attr.type="string"/>"#
+        )?;
+        writeln!(
+            out,
+            r#"  <key id="highlighted" for="all" attr.name="highlighted" attr.type="boolean"/>"#
+        )?;
+        writeln!(
+            out,
+            r#"  <key id="cluster" for="node" attr.name="cluster" attr.type="string"/>"#
+        )?;
+        let direction = if self.graph.undirected {
+            "undirected"
+        } else {
+            "directed"
+        };
+        writeln!(
+            out,
+            r#"  <graph id="{}" edgedefault="{}">"#,
+            xml_escape(&self.name),
+            direction
+        )?;
+        for (id, (vertex, label)) in vertices.iter().enumerate() {
+            writeln!(out, r#"    <node id="n{}">"#, id)?;
+            writeln!(
+                out,
+                r#"      <data key="label">{}</data>"#,
+                xml_escape(label)
+            )?;
+            if self.highlighted_vertices.contains(*vertex) {
+                writeln!(out, r#"      <data key="highlighted">true</data>"#)?;
+            }
+            if let Some(cluster) = &clusters[&id] {
+                writeln!(
+                    out,
+                    r#"      <data key="cluster">{}</data>"#,
+                    xml_escape(cluster)
+                )?;
+            }
+            writeln!(out, "    </node>")?;
+        }
+        for edge in &edges {
+            writeln!(
+                out,
+                r#"    <edge source="n{}" target="n{}">"#,
+                edge.from, edge.to
+            )?;
+            if let Some(label) = &edge.label {
+                writeln!(
+                    out,
+                    r#"      <data key="label">{}</data>"#,
+                    xml_escape(label)
+                )?;
+            }
+            if edge.highlighted {
+                writeln!(out, r#"      <data key="highlighted">true</data>"#)?;
+            }
+            writeln!(out, "    </edge>")?;
+        }
+        writeln!(out, "  </graph>")?;
+        writeln!(out, "</graphml>")
+    }
+}
+
+fn dot_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn mermaid_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "#quot;"))
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    pub fn exporter(&self) -> GraphExporter<'_, V, E> {
+        GraphExporter::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Graph, GraphExporter};
+    use std::io;
+
+    type Writer = fn(&GraphExporter<&'static str, u32>, &mut Vec<u8>) -> io::Result<()>;
+
+    // a - b - c, with a self-loop on c
+    fn sample(undirected: bool) -> Graph<&'static str, u32> {
+        let mut graph = if undirected {
+            Graph::new_undirected()
+        } else {
+            Graph::new()
+        };
+        for (from, to, weight) in [("b", "c", 2), ("a", "b", 1), ("c", "c", 3)] {
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+
+    fn render(graph: &Graph<&'static str, u32>, write: Writer) -> String {
+        let exporter = graph
+            .exporter()
+            .vertex_labels(|v| v.to_string())
+            .edge_labels(|w| w.to_string())
+            .highlight_edges([("b", "a")]);
+        let mut out = Vec::new();
+        write(&exporter, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn dot() {
+        assert_eq!(
+            render(&sample(false), |e, out| e.write_dot(out)),
+            r#"digraph "G" {
+  rankdir=LR;
+  n0 [label="a"];
+  n1 [label="b"];
+  n2 [label="c"];
+  n0 -> n1 [label="1"];
+  n1 -> n2 [label="2"];
+  n2 -> n2 [label="3"];
+}
+"#
+        );
+        assert_eq!(
+            render(&sample(true), |e, out| e.write_dot(out)),
+            r#"graph "G" {
+  rankdir=LR;
+  n0 [label="a"];
+  n1 [label="b"];
+  n2 [label="c"];
+  n0 -- n1 [label="1", color=red, penwidth=2];
+  n1 -- n2 [label="2"];
+  n2 -- n2 [label="3"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn mermaid() {
+        assert_eq!(
+            render(&sample(false), |e, out| e.write_mermaid(out)),
+            r#"flowchart LR
+  n0["a"]
+  n1["b"]
+  n2["c"]
+  n0 -->|"1"| n1
+  n1 -->|"2"| n2
+  n2 -->|"3"| n2
+"#
+        );
+        assert_eq!(
+            render(&sample(true), |e, out| e.write_mermaid(out)),
+            r#"flowchart LR
+  n0["a"]
+  n1["b"]
+  n2["c"]
+  n0 ---|"1"| n1
+  n1 ---|"2"| n2
+  n2 ---|"3"| n2
+  linkStyle 0 stroke:#f00,stroke-width:2px
+"#
+        );
+    }
+
+    #[test]
+    fn graphml() {
+        let header = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="label" for="all" attr.name="label" attr.type="string"/>
+  <key id="highlighted" for="all" attr.name="highlighted" attr.type="boolean"/>
+  <key id="cluster" for="node" attr.name="cluster" attr.type="string"/>
+"#;
+        let body = |direction: &str, highlight: &str| {
+            format!(
+                r#"  <graph id="G" edgedefault="{}">
+    <node id="n0">
+      <data key="label">a</data>
+    </node>
+    <node id="n1">
+      <data key="label">b</data>
+    </node>
+    <node id="n2">
+      <data key="label">c</data>
+    </node>
+    <edge source="n0" target="n1">
+      <data key="label">1</data>
+{}    </edge>
+    <edge source="n1" target="n2">
+      <data key="label">2</data>
+    </edge>
+    <edge source="n2" target="n2">
+      <data key="label">3</data>
+    </edge>
+  </graph>
+</graphml>
+"#,
+                direction, highlight
+            )
+        };
+        assert_eq!(
+            render(&sample(false), |e, out| e.write_graphml(out)),
+            format!("{}{}", header, body("directed", ""))
+        );
+        assert_eq!(
+            render(&sample(true), |e, out| e.write_graphml(out)),
+            format!(
+                "{}{}",
+                header,
+                body(
+                    "undirected",
+                    "      <data key=\"highlighted\">true</data>\n"
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn equal_labels_fall_back_to_debug_order() {
+        let mut graph = Graph::new();
+        graph.add_edge(3, 1, ());
+        graph.add_edge(2, 3, ());
+        let mut out = Vec::new();
+        graph
+            .exporter()
+            .node_attributes("shape=circle")
+            .vertex_labels(|_| "x".to_string())
+            .write_dot(&mut out)
+            .unwrap();
+        // Ids follow 1, 2, 3, so 3 -> 1 is n2 -> n0
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"digraph "G" {
+  rankdir=LR;
+  node [shape=circle];
+  n0 [label="x"];
+  n1 [label="x"];
+  n2 [label="x"];
+  n1 -> n2;
+  n2 -> n0;
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use crate::Graph;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+// Result of a max-flow computation. `cut` lists the saturated edges leaving `source_side`,
+// whose capacities sum to `value`
+#[derive(Debug, Clone)]
+pub struct Flow<V, W> {
+    pub value: W,
+    pub edge_flows: HashMap<(V, V), W>,
+    pub source_side: HashSet<V>,
+    pub cut: Vec<(V, V)>,
+}
+
+// Residual network over dense vertex ids. Arc `i ^ 1` is the reverse of arc `i`
+struct Residual<W> {
+    targets: Vec<usize>,
+    capacities: Vec<W>,
+    out: Vec<Vec<usize>>,
+}
+
+impl<W> Residual<W>
+where
+    W: Copy + Ord + Default + Add<Output = W> + Sub<Output = W>,
+{
+    fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.out.len()];
+        let mut queue = VecDeque::new();
+        levels[source] = Some(0);
+        queue.push_back(source);
+        while let Some(v) = queue.pop_front() {
+            for arc in &self.out[v] {
+                let to = self.targets[*arc];
+                if levels[to].is_none() && self.capacities[*arc] > W::default() {
+                    levels[to] = Some(levels[v].unwrap() + 1);
+                    queue.push_back(to);
+                }
+            }
+        }
+        levels
+    }
+
+    // Finds one path from `source` to `sink` along the level graph and pushes its bottleneck.
+    // The path is kept as a stack of arcs instead of recursing, for long chains
+    fn augment(
+        &mut self,
+        source: usize,
+        sink: usize,
+        levels: &[Option<usize>],
+        next_arc: &mut [usize],
+    ) -> W {
+        let mut path: Vec<usize> = Vec::new();
+        let mut v = source;
+        loop {
+            if v == sink {
+                let pushed = path
+                    .iter()
+                    .map(|arc| self.capacities[*arc])
+                    .min()
+                    .unwrap_or_default();
+                for arc in path {
+                    self.capacities[arc] = self.capacities[arc] - pushed;
+                    self.capacities[arc ^ 1] = self.capacities[arc ^ 1] + pushed;
+                }
+                return pushed;
+            }
+            let advance = self.out[v][next_arc[v]..].iter().position(|arc| {
+                self.capacities[*arc] > W::default()
+                    && levels[self.targets[*arc]] == levels[v].map(|l| l + 1)
+            });
+            match advance {
+                Some(skipped) => {
+                    next_arc[v] += skipped;
+                    let arc = self.out[v][next_arc[v]];
+                    path.push(arc);
+                    v = self.targets[arc];
+                }
+                None => {
+                    // Dead end: retreat and move the previous vertex past the arc into it
+                    next_arc[v] = self.out[v].len();
+                    let Some(arc) = path.pop() else {
+                        return W::default();
+                    };
+                    v = self.targets[arc ^ 1];
+                    next_arc[v] += 1;
+                }
+            }
+        }
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    // Dinic's algorithm. In an undirected graph each direction of an edge gets the full capacity
+    pub fn max_flow<W>(&self, source: &V, sink: &V, capacity: impl Fn(&E) -> W) -> Flow<V, W>
+    where
+        W: Copy + Ord + Default + Add<Output = W> + Sub<Output = W>,
+    {
+        let dense = self.to_dense();
+        let count = dense.vertex_count();
+        let mut residual = Residual {
+            targets: Vec::new(),
+            capacities: Vec::new(),
+            out: vec![Vec::new(); count],
+        };
+        // (from, to, original capacity) of each edge. Its forward arc is at index 2 * i
+        let mut originals = Vec::new();
+        for from in 0..count {
+            for (to, edge) in dense.edges_from(from as u32) {
+                let (to, cap) = (to as usize, capacity(edge));
+                let forward = residual.targets.len();
+                residual.targets.extend([to, from]);
+                residual.capacities.extend([cap, W::default()]);
+                residual.out[from].push(forward);
+                residual.out[to].push(forward + 1);
+                originals.push((from, to, cap));
+            }
+        }
+
+        let mut value = W::default();
+        if let (Some(s), Some(t)) = (dense.id(source), dense.id(sink)) {
+            let (s, t) = (s as usize, t as usize);
+            let mut levels = residual.levels(s);
+            while s != t && levels[t].is_some() {
+                let mut next_arc = vec![0; count];
+                loop {
+                    let pushed = residual.augment(s, t, &levels, &mut next_arc);
+                    if pushed == W::default() {
+                        break;
+                    }
+                    value = value + pushed;
+                }
+                levels = residual.levels(s);
+            }
+        }
+
+        let reachable = match dense.id(source) {
+            Some(s) => residual.levels(s as usize),
+            None => vec![None; count],
+        };
+        let vertex = |id: usize| dense.value(id as u32).clone();
+        let mut edge_flows = HashMap::new();
+        let mut cut = Vec::new();
+        for (i, (from, to, cap)) in originals.into_iter().enumerate() {
+            let flow = cap - residual.capacities[2 * i];
+            if flow > W::default() {
+                edge_flows.insert((vertex(from), vertex(to)), flow);
+            }
+            if reachable[from].is_some() && reachable[to].is_none() {
+                cut.push((vertex(from), vertex(to)));
+            }
+        }
+        Flow {
+            value,
+            edge_flows,
+            source_side: (0..count)
+                .filter(|v| reachable[*v].is_some())
+                .map(vertex)
+                .collect(),
+            cut,
+        }
+    }
+
+    // Hopcroft–Karp maximum matching between `left` and the vertices its edges lead to.
+    // Returns (left, right) pairs
+    pub fn max_bipartite_matching<'a>(&self, left: impl IntoIterator<Item = &'a V>) -> Vec<(V, V)>
+    where
+        V: 'a,
+    {
+        let dense = self.to_dense();
+        let left = left
+            .into_iter()
+            .filter_map(|v| dense.id(v))
+            .collect::<Vec<_>>();
+        let count = dense.vertex_count();
+        let mut match_left: HashMap<u32, u32> = HashMap::new();
+        let mut match_right: HashMap<u32, u32> = HashMap::new();
+
+        loop {
+            // Layer free left vertices at 0, then alternate unmatched and matched edges
+            let mut distance = vec![usize::MAX; count];
+            let mut queue = VecDeque::new();
+            for u in &left {
+                if !match_left.contains_key(u) {
+                    distance[*u as usize] = 0;
+                    queue.push_back(*u);
+                }
+            }
+            let mut found = false;
+            while let Some(u) = queue.pop_front() {
+                for v in dense.neighbors(u) {
+                    match match_right.get(v) {
+                        None => found = true,
+                        Some(w) if distance[*w as usize] == usize::MAX => {
+                            distance[*w as usize] = distance[u as usize] + 1;
+                            queue.push_back(*w);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if !found {
+                break;
+            }
+            let mut next_edge = vec![0; count];
+            for u in &left {
+                if !match_left.contains_key(u) {
+                    hopcroft_karp_augment(
+                        &dense,
+                        *u,
+                        &mut distance,
+                        &mut next_edge,
+                        &mut match_left,
+                        &mut match_right,
+                    );
+                }
+            }
+        }
+
+        match_left
+            .into_iter()
+            .map(|(u, v)| (dense.value(u).clone(), dense.value(v).clone()))
+            .collect()
+    }
+}
+
+// Depth first search for an augmenting path from the free vertex `start`, flipping the
+// matching along it if one is found. The path is kept as a stack of (left, right) steps
+// instead of recursing, for long chains
+fn hopcroft_karp_augment<V, E>(
+    dense: &crate::DenseGraph<V, E>,
+    start: u32,
+    distance: &mut [usize],
+    next_edge: &mut [usize],
+    match_left: &mut HashMap<u32, u32>,
+    match_right: &mut HashMap<u32, u32>,
+) -> bool
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    let mut path: Vec<(u32, u32)> = Vec::new();
+    let mut u = start;
+    loop {
+        let Some(v) = dense.neighbors(u).get(next_edge[u as usize]).copied() else {
+            // Dead end: take u out of the layered graph for the rest of this phase
+            distance[u as usize] = usize::MAX;
+            let Some((previous, _)) = path.pop() else {
+                return false;
+            };
+            u = previous;
+            continue;
+        };
+        next_edge[u as usize] += 1;
+        match match_right.get(&v).copied() {
+            None => {
+                path.push((u, v));
+                for (u, v) in path {
+                    match_left.insert(u, v);
+                    match_right.insert(v, u);
+                }
+                return true;
+            }
+            Some(w) if distance[w as usize] == distance[u as usize] + 1 => {
+                path.push((u, v));
+                u = w;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Graph;
+    use std::collections::HashSet;
+
+    // The flow network from CLRS, with a maximum flow of 23
+    fn network() -> Graph<&'static str, u32> {
+        let mut graph = Graph::new();
+        for (from, to, capacity) in [
+            ("s", "v1", 16),
+            ("s", "v2", 13),
+            ("v2", "v1", 4),
+            ("v1", "v3", 12),
+            ("v3", "v2", 9),
+            ("v2", "v4", 14),
+            ("v4", "v3", 7),
+            ("v3", "t", 20),
+            ("v4", "t", 4),
+        ] {
+            graph.add_edge(from, to, capacity);
+        }
+        graph
+    }
+
+    #[test]
+    fn max_flow_value() {
+        let flow = network().max_flow(&"s", &"t", |c| *c);
+        assert_eq!(flow.value, 23);
+    }
+
+    #[test]
+    fn flow_is_conserved_and_within_capacity() {
+        let graph = network();
+        let flow = graph.max_flow(&"s", &"t", |c| *c);
+        for ((from, to), f) in &flow.edge_flows {
+            assert!(*f <= *graph.edge(from, to).unwrap());
+        }
+        for vertex in ["v1", "v2", "v3", "v4"] {
+            let incoming: u32 = flow
+                .edge_flows
+                .iter()
+                .filter(|((_, to), _)| *to == vertex)
+                .map(|(_, f)| f)
+                .sum();
+            let outgoing: u32 = flow
+                .edge_flows
+                .iter()
+                .filter(|((from, _), _)| *from == vertex)
+                .map(|(_, f)| f)
+                .sum();
+            assert_eq!(incoming, outgoing, "flow not conserved at {}", vertex);
+        }
+    }
+
+    #[test]
+    fn min_cut_matches_flow() {
+        let graph = network();
+        let flow = graph.max_flow(&"s", &"t", |c| *c);
+        let cut_capacity: u32 = flow
+            .cut
+            .iter()
+            .map(|(from, to)| graph.edge(from, to).unwrap())
+            .sum();
+        assert_eq!(cut_capacity, flow.value);
+        assert!(flow.source_side.contains("s"));
+        assert!(!flow.source_side.contains("t"));
+    }
+
+    #[test]
+    fn unreachable_sink_has_no_flow() {
+        let mut graph = network();
+        graph.add_vertex("island");
+        let flow = graph.max_flow(&"s", &"island", |c| *c);
+        assert_eq!(flow.value, 0);
+        assert!(flow.edge_flows.is_empty());
+    }
+
+    #[test]
+    fn long_chain_does_not_overflow_the_stack() {
+        let length = 20_000;
+        std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(move || {
+                let mut graph = Graph::new();
+                for i in 1..length {
+                    graph.add_edge(i - 1, i, 3);
+                }
+                let flow = graph.max_flow(&0, &(length - 1), |c| *c);
+                assert_eq!(flow.value, 3);
+
+                // A long alternating chain a0 - b0 - a1 - b1 - ..., depending on which
+                // edges the first phase picks
+                let mut graph = Graph::new();
+                for i in 0..length {
+                    graph.add_edge(("a", i), ("b", i), ());
+                    if i > 0 {
+                        graph.add_edge(("a", i), ("b", i - 1), ());
+                    }
+                }
+                let left = (0..length).map(|i| ("a", i)).collect::<Vec<_>>();
+                assert_eq!(graph.max_bipartite_matching(left.iter()).len(), length);
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn bipartite_matching() {
+        let mut graph = Graph::new();
+        for (worker, job) in [("a", 1), ("a", 2), ("b", 1), ("c", 2), ("c", 3), ("d", 3)] {
+            graph.add_edge(worker.to_string(), job.to_string(), ());
+        }
+        let workers = ["a", "b", "c", "d"].map(|w| w.to_string());
+        let matching = graph.max_bipartite_matching(workers.iter());
+        assert_eq!(matching.len(), 3);
+        let jobs = matching.iter().map(|(_, job)| job).collect::<HashSet<_>>();
+        assert_eq!(jobs.len(), 3);
+        for (worker, job) in &matching {
+            assert!(graph.contains_edge(worker, job));
+        }
+    }
+}
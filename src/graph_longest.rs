@@ -0,0 +1,365 @@
+use crate::{DenseGraph, Graph, WeightedPath};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Add;
+
+// Dense copy of a graph for exhaustive searches, with each edge's weight worked out once:
+// `out[i]` lists the (target, weight) pairs leaving vertex id `i`
+struct Indexed<V, E, W> {
+    dense: DenseGraph<V, E>,
+    out: Vec<Vec<(usize, W)>>,
+}
+
+impl<V, E, W> Indexed<V, E, W>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+    W: Copy,
+{
+    fn new(graph: &Graph<V, E>, weight: impl Fn(&E) -> W) -> Self {
+        let dense = graph.to_dense();
+        let out = (0..dense.vertex_count() as u32)
+            .map(|v| {
+                dense
+                    .edges_from(v)
+                    .map(|(to, edge)| (to as usize, weight(edge)))
+                    .collect()
+            })
+            .collect();
+        Indexed { dense, out }
+    }
+
+    fn len(&self) -> usize {
+        self.dense.vertex_count()
+    }
+
+    fn edge(&self, from: usize, to: usize) -> Option<W> {
+        self.out[from]
+            .iter()
+            .find(|(n, _)| *n == to)
+            .map(|(_, w)| *w)
+    }
+
+    fn to_path(&self, distance: W, route: &[usize]) -> WeightedPath<V, E, W> {
+        WeightedPath {
+            distance,
+            vertices: route
+                .iter()
+                .map(|i| self.dense.value(*i as u32).clone())
+                .collect(),
+            edges: route
+                .windows(2)
+                .map(|pair| {
+                    self.dense
+                        .edge(pair[0] as u32, pair[1] as u32)
+                        .unwrap()
+                        .clone()
+                })
+                .collect(),
+        }
+    }
+}
+
+// Marks table entries that no route has reached yet
+const UNREACHED: u8 = u8::MAX;
+
+struct LongestSearch<W> {
+    end: usize,
+    // Heaviest edge into each vertex, for bounding what the unvisited vertices can still add
+    best_into: Vec<W>,
+    best: Option<(W, Vec<usize>)>,
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    // Heaviest path from `start` to `end` that never revisits a vertex. Exhaustive search
+    // over visited sets, so only practical on small graphs like junction graphs of mazes.
+    // Weights must not be negative
+    pub fn longest_path<W>(
+        &self,
+        start: &V,
+        end: &V,
+        weight: impl Fn(&E) -> W,
+    ) -> Option<WeightedPath<V, E, W>>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        let indexed = Indexed::new(self, weight);
+        assert!(
+            indexed.len() <= 128,
+            "Longest path search supports at most 128 vertices, got {}",
+            indexed.len()
+        );
+        let start = indexed.dense.id(start)? as usize;
+        let end = indexed.dense.id(end)? as usize;
+        let mut best_into = vec![W::default(); indexed.len()];
+        for edges in &indexed.out {
+            for (to, w) in edges {
+                best_into[*to] = best_into[*to].max(*w);
+            }
+        }
+        let mut search = LongestSearch {
+            end,
+            best_into,
+            best: None,
+        };
+        let mut route = vec![start];
+        longest_from(&indexed, &mut search, &mut route, 1 << start, W::default());
+        search
+            .best
+            .map(|(distance, route)| indexed.to_path(distance, &route))
+    }
+
+    // Heaviest path from `start` to `end` in a DAG, following the topological order.
+    // Errors if the graph has a cycle
+    pub fn dag_longest_path<W>(
+        &self,
+        start: &V,
+        end: &V,
+        weight: impl Fn(&E) -> W,
+    ) -> Result<Option<WeightedPath<V, E, W>>, String>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        let order = self.kahn(|_| 0)?;
+        let indexed = Indexed::new(self, weight);
+        let (Some(start), Some(end)) = (indexed.dense.id(start), indexed.dense.id(end)) else {
+            return Ok(None);
+        };
+        // Best distance to each vertex id, and the id it was reached from
+        let mut best: Vec<Option<(W, usize)>> = vec![None; indexed.len()];
+        best[start as usize] = Some((W::default(), start as usize));
+        for vertex in order.iter().map(|v| indexed.dense.id(v).unwrap() as usize) {
+            let Some((distance, _)) = best[vertex] else {
+                continue;
+            };
+            for (neighbor, w) in &indexed.out[vertex] {
+                let candidate = distance + *w;
+                if best[*neighbor].is_none_or(|(d, _)| candidate > d) {
+                    best[*neighbor] = Some((candidate, vertex));
+                }
+            }
+        }
+        let Some((distance, _)) = best[end as usize] else {
+            return Ok(None);
+        };
+        let mut route = vec![end as usize];
+        while *route.last().unwrap() != start as usize {
+            route.push(best[*route.last().unwrap()].unwrap().1);
+        }
+        route.reverse();
+        Ok(Some(indexed.to_path(distance, &route)))
+    }
+
+    // Cheapest route through every vertex exactly once (Held–Karp). With `round_trip` the
+    // route also returns to where it started. The table takes 2^n * n * (size of W + 1) bytes,
+    // about 190 MB for 20 vertices with 8 byte weights, so at most 20 vertices
+    pub fn shortest_tour<W>(
+        &self,
+        weight: impl Fn(&E) -> W,
+        round_trip: bool,
+    ) -> Option<WeightedPath<V, E, W>>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        self.held_karp(weight, round_trip, |a, b| a < b)
+    }
+
+    pub fn longest_tour<W>(
+        &self,
+        weight: impl Fn(&E) -> W,
+        round_trip: bool,
+    ) -> Option<WeightedPath<V, E, W>>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        self.held_karp(weight, round_trip, |a, b| a > b)
+    }
+
+    fn held_karp<W>(
+        &self,
+        weight: impl Fn(&E) -> W,
+        round_trip: bool,
+        better: impl Fn(W, W) -> bool,
+    ) -> Option<WeightedPath<V, E, W>>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        let indexed = Indexed::new(self, weight);
+        let n = indexed.len();
+        assert!(
+            n <= 20,
+            "Tour search supports at most 20 vertices, got {}",
+            n
+        );
+        if n == 0 {
+            return None;
+        }
+        // For the best route visiting exactly `mask` and ending at v, its distance is at
+        // distance[mask * n + v] and the vertex before v at parent[mask * n + v]
+        let mut distance = vec![W::default(); n << n];
+        let mut parent = vec![UNREACHED; n << n];
+        if round_trip {
+            // Every vertex is on the cycle, so it might as well start at 0
+            parent[n] = 0;
+        } else {
+            for v in 0..n {
+                parent[(n << v) + v] = v as u8;
+            }
+        }
+        for mask in 1..(1usize << n) {
+            for v in 0..n {
+                let at = mask * n + v;
+                if parent[at] == UNREACHED {
+                    continue;
+                }
+                for (u, w) in &indexed.out[v] {
+                    if mask & (1 << u) != 0 {
+                        continue;
+                    }
+                    let next = (mask | (1 << u)) * n + u;
+                    let candidate = distance[at] + *w;
+                    if parent[next] == UNREACHED || better(candidate, distance[next]) {
+                        distance[next] = candidate;
+                        parent[next] = v as u8;
+                    }
+                }
+            }
+        }
+
+        let full = (1 << n) - 1;
+        let (total, last) = (0..n)
+            .filter_map(|v| {
+                let at = full * n + v;
+                if parent[at] == UNREACHED {
+                    return None;
+                }
+                if round_trip {
+                    let w = indexed.edge(v, 0)?;
+                    Some((distance[at] + w, v))
+                } else {
+                    Some((distance[at], v))
+                }
+            })
+            .reduce(|a, b| if better(b.0, a.0) { b } else { a })?;
+
+        let mut route = vec![last];
+        let (mut mask, mut v) = (full, last);
+        while mask.count_ones() > 1 {
+            let from = parent[mask * n + v] as usize;
+            mask &= !(1 << v);
+            v = from;
+            route.push(v);
+        }
+        route.reverse();
+        if round_trip {
+            route.push(route[0]);
+        }
+        Some(indexed.to_path(total, &route))
+    }
+}
+
+fn longest_from<V, E, W>(
+    indexed: &Indexed<V, E, W>,
+    search: &mut LongestSearch<W>,
+    route: &mut Vec<usize>,
+    visited: u128,
+    distance: W,
+) where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+    W: Copy + Ord + Default + Add<Output = W>,
+{
+    let vertex = *route.last().unwrap();
+    if vertex == search.end {
+        if search
+            .best
+            .as_ref()
+            .is_none_or(|(best, _)| distance > *best)
+        {
+            search.best = Some((distance, route.clone()));
+        }
+        return;
+    }
+    if let Some((best, _)) = &search.best {
+        let bound = (0..indexed.len())
+            .filter(|v| visited & (1 << v) == 0)
+            .fold(distance, |total, v| total + search.best_into[v]);
+        if bound <= *best {
+            return;
+        }
+    }
+    for (next, w) in &indexed.out[vertex] {
+        if visited & (1 << next) != 0 {
+            continue;
+        }
+        route.push(*next);
+        longest_from(indexed, search, route, visited | (1 << next), distance + *w);
+        route.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Graph;
+
+    #[test]
+    fn dag_longest_path_prefers_heavier_route() {
+        let mut graph = Graph::new();
+        for (from, to, weight) in [("a", "b", 1), ("b", "d", 1), ("a", "c", 2), ("c", "d", 3)] {
+            graph.add_edge(from, to, weight);
+        }
+        let path = graph.dag_longest_path(&"a", &"d", |w| *w).unwrap().unwrap();
+        assert_eq!(path.distance, 5);
+        assert_eq!(path.vertices, vec!["a", "c", "d"]);
+        assert_eq!(path.edges, vec![2, 3]);
+        assert!(graph
+            .dag_longest_path(&"d", &"a", |w| *w)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn dag_longest_path_rejects_cycles() {
+        let mut graph = Graph::new();
+        for (from, to) in [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")] {
+            graph.add_edge(from, to, 1);
+        }
+        assert!(graph.dag_longest_path(&"a", &"d", |w| *w).is_err());
+    }
+
+    #[test]
+    fn tours_of_a_square() {
+        let mut graph = Graph::new_undirected();
+        for (from, to, weight) in [
+            ("a", "b", 1),
+            ("b", "c", 1),
+            ("c", "d", 1),
+            ("d", "a", 1),
+            ("a", "c", 5),
+            ("b", "d", 5),
+        ] {
+            graph.add_edge(from, to, weight);
+        }
+        let round = graph.shortest_tour(|w| *w, true).unwrap();
+        assert_eq!(round.distance, 4);
+        assert_eq!(round.vertices.len(), 5);
+        assert_eq!(round.vertices.first(), round.vertices.last());
+        assert_eq!(graph.shortest_tour(|w| *w, false).unwrap().distance, 3);
+        assert_eq!(graph.longest_tour(|w| *w, false).unwrap().distance, 11);
+        assert_eq!(graph.longest_tour(|w| *w, true).unwrap().distance, 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 20 vertices")]
+    fn tours_refuse_large_graphs() {
+        let mut graph = Graph::new();
+        for i in 1..30 {
+            graph.add_edge(i - 1, i, 1);
+        }
+        graph.shortest_tour(|w| *w, false);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::graph_paths::Frontier;
+use crate::Graph;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+impl<V, E> Graph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    // Kahn's algorithm. Whenever several vertices are ready, the one with the lowest
+    // `priority` goes first
+    pub(crate) fn kahn<K: Ord>(&self, priority: impl Fn(&V) -> K) -> Result<Vec<V>, String> {
+        let vertices = self.vertex_set();
+        let mut in_degrees: HashMap<&V, usize> = vertices.iter().map(|v| (*v, 0)).collect();
+        for to in self.edges.values().flat_map(|edges| edges.keys()) {
+            *in_degrees.get_mut(to).unwrap() += 1;
+        }
+
+        let mut ready = Frontier::new();
+        for (vertex, degree) in &in_degrees {
+            if *degree == 0 {
+                ready.push(priority(vertex), (*vertex).clone());
+            }
+        }
+        let mut order = Vec::new();
+        while let Some((_, vertex)) = ready.pop() {
+            let vertex = vertex.clone();
+            for neighbor in self.neighbors(&vertex) {
+                let degree = in_degrees.get_mut(neighbor).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(priority(neighbor), neighbor.clone());
+                }
+            }
+            order.push(vertex);
+        }
+
+        if order.len() == vertices.len() {
+            Ok(order)
+        } else {
+            Err(format!(
+                "Graph has a cycle: {:?}",
+                self.find_cycle().unwrap_or_default()
+            ))
+        }
+    }
+
+    // Every vertex, with each edge pointing forwards. Ties go to the smallest vertex
+    pub fn topological_sort(&self) -> Result<Vec<V>, String>
+    where
+        V: Ord,
+    {
+        self.kahn(|v| v.clone())
+    }
+
+    pub fn is_acyclic(&self) -> bool {
+        self.find_cycle().is_none()
+    }
+
+    // Vertices of some cycle in order, with an edge from the last back to the first.
+    // In an undirected graph going straight back over the same edge doesn't count
+    pub fn find_cycle(&self) -> Option<Vec<V>> {
+        let mut done = HashSet::new();
+        for vertex in self.vertex_set() {
+            if !done.contains(vertex) {
+                if let Some(cycle) = self.cycle_from(vertex, &mut done) {
+                    return Some(cycle);
+                }
+            }
+        }
+        None
+    }
+
+    fn cycle_from<'a>(&'a self, root: &'a V, done: &mut HashSet<&'a V>) -> Option<Vec<V>> {
+        // The current path with each vertex's position on it, and a (parent, remaining neighbors)
+        // frame per vertex on the path instead of recursion, for long chains
+        let mut path: Vec<&'a V> = vec![root];
+        let mut on_path: HashMap<&'a V, usize> = HashMap::from([(root, 0)]);
+        let mut frames = vec![(None, self.neighbors(root).collect_vec())];
+        while let Some((parent, remaining)) = frames.last_mut() {
+            let vertex = *path.last().unwrap();
+            let Some(neighbor) = remaining.pop() else {
+                frames.pop();
+                path.pop();
+                on_path.remove(vertex);
+                done.insert(vertex);
+                continue;
+            };
+            if self.undirected && Some(neighbor) == *parent {
+                continue;
+            }
+            if let Some(start) = on_path.get(neighbor) {
+                return Some(path[*start..].iter().map(|v| (*v).clone()).collect());
+            }
+            if !done.contains(neighbor) {
+                on_path.insert(neighbor, path.len());
+                path.push(neighbor);
+                frames.push((Some(vertex), self.neighbors(neighbor).collect_vec()));
+            }
+        }
+        None
+    }
+
+    // The graph restricted to `vertices` and the edges between them
+    pub fn induced_subgraph(&self, vertices: &HashSet<V>) -> Graph<V, E> {
+        let edges = self
+            .edges
+            .iter()
+            .filter(|(from, _)| vertices.contains(*from))
+            .map(|(from, edges)| {
+                let kept = edges
+                    .iter()
+                    .filter(|(to, _)| vertices.contains(*to))
+                    .map(|(to, edge)| (to.clone(), edge.clone()))
+                    .collect();
+                (from.clone(), kept)
+            })
+            .collect();
+        Graph {
+            edges,
+            undirected: self.undirected,
+        }
+    }
+
+    // Reorders `sequence` so that every edge between its items points forwards, treating
+    // edges as "must come before" rules. Items keep their relative order where the rules allow
+    pub fn sort_by_rules(&self, sequence: &[V]) -> Result<Vec<V>, String> {
+        let position: HashMap<&V, usize> =
+            sequence.iter().enumerate().map(|(i, v)| (v, i)).collect();
+        let mut subgraph = self.induced_subgraph(&sequence.iter().cloned().collect());
+        for item in sequence {
+            subgraph.add_vertex(item.clone());
+        }
+        subgraph.kahn(|v| position[v])
+    }
+
+    // Whether no edge between items of `sequence` points backwards
+    pub fn follows_rules(&self, sequence: &[V]) -> bool {
+        let position: HashMap<&V, usize> =
+            sequence.iter().enumerate().map(|(i, v)| (v, i)).collect();
+        sequence.iter().enumerate().all(|(i, item)| {
+            self.neighbors(item)
+                .all(|next| position.get(next).is_none_or(|j| *j > i))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Graph;
+
+    #[test]
+    fn finds_cycle_in_order() {
+        let mut graph = Graph::new();
+        for (from, to) in [(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)] {
+            graph.add_edge(from, to, ());
+        }
+        let mut cycle = graph.find_cycle().unwrap();
+        let first = cycle.iter().position(|v| *v == 1).unwrap();
+        cycle.rotate_left(first);
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert!(graph.topological_sort().is_err());
+    }
+
+    #[test]
+    fn long_chain_does_not_overflow_the_stack() {
+        let length = 20_000;
+        std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(move || {
+                let mut graph = Graph::new();
+                for i in 1..length {
+                    graph.add_edge(i - 1, i, ());
+                }
+                assert!(graph.is_acyclic());
+                graph.add_edge(length - 1, 0, ());
+                assert_eq!(graph.find_cycle().unwrap().len(), length);
+                assert!(graph.topological_sort().is_err());
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+}
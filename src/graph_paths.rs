@@ -0,0 +1,328 @@
+use crate::Graph;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Add;
+
+// A route through a graph. `edges[i]` is the edge taken from `vertices[i]` to `vertices[i + 1]`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct WeightedPath<V, E, W> {
+    pub distance: W,
+    pub vertices: Vec<V>,
+    pub edges: Vec<E>,
+}
+
+// Distance to each reached vertex, and the vertex and edge it was reached through
+type Distances<V, E, W> = (HashMap<V, W>, HashMap<V, (V, E)>);
+
+// Min-heap over vertices that aren't Ord themselves: entries point into `vertices` instead
+pub(crate) struct Frontier<V, W> {
+    heap: BinaryHeap<Reverse<(W, usize)>>,
+    vertices: Vec<V>,
+}
+
+impl<V, W: Ord> Frontier<V, W> {
+    pub(crate) fn new() -> Frontier<V, W> {
+        Frontier {
+            heap: BinaryHeap::new(),
+            vertices: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, priority: W, vertex: V) {
+        self.heap.push(Reverse((priority, self.vertices.len())));
+        self.vertices.push(vertex);
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<(W, &V)> {
+        let Reverse((priority, index)) = self.heap.pop()?;
+        Some((priority, &self.vertices[index]))
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Eq + Hash + Clone + Debug,
+    E: Eq + Hash + Clone + Debug,
+{
+    fn neighbors_of<'a>(&'a self, vertex: &V) -> impl Iterator<Item = (&'a V, &'a E)> {
+        self.edges
+            .get(vertex)
+            .into_iter()
+            .flat_map(|edges| edges.iter())
+    }
+
+    // Every vertex, including sinks that only ever appear as the target of an edge
+    pub(crate) fn vertex_set(&self) -> HashSet<&V> {
+        self.edges
+            .iter()
+            .flat_map(|(from, edges)| std::iter::once(from).chain(edges.keys()))
+            .collect()
+    }
+
+    pub(crate) fn rebuild_path<W: Copy>(
+        &self,
+        start: &V,
+        end: &V,
+        distance: W,
+        previous: &HashMap<V, (V, E)>,
+    ) -> WeightedPath<V, E, W> {
+        let mut vertices = vec![end.clone()];
+        let mut edges = Vec::new();
+        let mut current = end;
+        while current != start {
+            let (from, edge) = &previous[current];
+            vertices.push(from.clone());
+            edges.push(edge.clone());
+            current = from;
+        }
+        vertices.reverse();
+        edges.reverse();
+        WeightedPath {
+            distance,
+            vertices,
+            edges,
+        }
+    }
+
+    // Best-first search shared by dijkstra and a_star. Weights must not be negative. A vertex
+    // is expanded again whenever a cheaper route to it turns up, so the heuristic only has to
+    // be admissible rather than consistent
+    fn best_first<W>(
+        &self,
+        start: &V,
+        end: Option<&V>,
+        weight: impl Fn(&E) -> W,
+        heuristic: impl Fn(&V) -> W,
+    ) -> Distances<V, E, W>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        let mut distances = HashMap::new();
+        let mut previous: HashMap<V, (V, E)> = HashMap::new();
+        let mut frontier = Frontier::new();
+        distances.insert(start.clone(), W::default());
+        frontier.push((heuristic(start), W::default()), start.clone());
+
+        while let Some(((_, distance), vertex)) = frontier.pop() {
+            // Stale entry, superseded by a cheaper route pushed since
+            if distances[vertex] < distance {
+                continue;
+            }
+            let vertex = vertex.clone();
+            if Some(&vertex) == end {
+                break;
+            }
+            for (neighbor, edge) in self.neighbors_of(&vertex) {
+                let candidate = distance + weight(edge);
+                if distances.get(neighbor).is_none_or(|d| candidate < *d) {
+                    distances.insert(neighbor.clone(), candidate);
+                    previous.insert(neighbor.clone(), (vertex.clone(), edge.clone()));
+                    frontier.push(
+                        (candidate + heuristic(neighbor), candidate),
+                        neighbor.clone(),
+                    );
+                }
+            }
+        }
+        (distances, previous)
+    }
+
+    pub fn dijkstra<W>(
+        &self,
+        start: &V,
+        end: &V,
+        weight: impl Fn(&E) -> W,
+    ) -> Option<WeightedPath<V, E, W>>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        self.a_star(start, end, weight, |_| W::default())
+    }
+
+    // Shortest distance from `start` to every reachable vertex
+    pub fn dijkstra_distances<W>(&self, start: &V, weight: impl Fn(&E) -> W) -> HashMap<V, W>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        self.best_first(start, None, weight, |_| W::default()).0
+    }
+
+    // `heuristic` must never overestimate the remaining distance to `end`. A consistent one
+    // (never dropping by more than an edge's weight) also avoids expanding vertices twice
+    pub fn a_star<W>(
+        &self,
+        start: &V,
+        end: &V,
+        weight: impl Fn(&E) -> W,
+        heuristic: impl Fn(&V) -> W,
+    ) -> Option<WeightedPath<V, E, W>>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        let (distances, previous) = self.best_first(start, Some(end), &weight, heuristic);
+        distances.get(end)?;
+        let mut path = self.rebuild_path(start, end, W::default(), &previous);
+        path.distance = path
+            .edges
+            .iter()
+            .fold(W::default(), |total, edge| total + weight(edge));
+        Some(path)
+    }
+
+    // Handles negative weights. Errors if a negative cycle is reachable from `start`
+    pub fn bellman_ford_distances<W>(
+        &self,
+        start: &V,
+        weight: impl Fn(&E) -> W,
+    ) -> Result<HashMap<V, W>, String>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        self.bellman_ford_search(start, weight)
+            .map(|(distances, _)| distances)
+    }
+
+    pub fn bellman_ford<W>(
+        &self,
+        start: &V,
+        end: &V,
+        weight: impl Fn(&E) -> W,
+    ) -> Result<Option<WeightedPath<V, E, W>>, String>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        let (distances, previous) = self.bellman_ford_search(start, weight)?;
+        Ok(distances
+            .get(end)
+            .map(|distance| self.rebuild_path(start, end, *distance, &previous)))
+    }
+
+    fn bellman_ford_search<W>(
+        &self,
+        start: &V,
+        weight: impl Fn(&E) -> W,
+    ) -> Result<Distances<V, E, W>, String>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        let mut distances = HashMap::new();
+        let mut previous = HashMap::new();
+        distances.insert(start.clone(), W::default());
+
+        let vertex_count = self.vertex_set().len();
+        // Without negative cycles the distances settle after vertex_count - 1 rounds, so the
+        // last round only confirms nothing changes any more
+        for _ in 0..=vertex_count {
+            let mut changed = false;
+            for (from, edges) in &self.edges {
+                let Some(distance) = distances.get(from).copied() else {
+                    continue;
+                };
+                for (to, edge) in edges {
+                    let candidate = distance + weight(edge);
+                    if distances.get(to).is_none_or(|d| candidate < *d) {
+                        distances.insert(to.clone(), candidate);
+                        previous.insert(to.clone(), (from.clone(), edge.clone()));
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                return Ok((distances, previous));
+            }
+        }
+        Err(format!("Negative cycle reachable from {:?}", start))
+    }
+
+    // Every path from `start` to `end` that ties for the shortest distance. Weights must be
+    // positive, as zero weight cycles would make the number of paths unbounded
+    pub fn all_shortest_paths<W>(
+        &self,
+        start: &V,
+        end: &V,
+        weight: impl Fn(&E) -> W,
+    ) -> Vec<WeightedPath<V, E, W>>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+    {
+        let mut distances = HashMap::new();
+        let mut previous: HashMap<V, Vec<(V, E)>> = HashMap::new();
+        let mut done = HashSet::new();
+        let mut frontier = Frontier::new();
+        distances.insert(start.clone(), W::default());
+        frontier.push(W::default(), start.clone());
+
+        while let Some((distance, vertex)) = frontier.pop() {
+            let vertex = vertex.clone();
+            if !done.insert(vertex.clone()) {
+                continue;
+            }
+            for (neighbor, edge) in self.neighbors_of(&vertex) {
+                let candidate = distance + weight(edge);
+                match distances.get(neighbor) {
+                    Some(d) if candidate > *d => continue,
+                    Some(d) if candidate == *d => {
+                        previous
+                            .entry(neighbor.clone())
+                            .or_default()
+                            .push((vertex.clone(), edge.clone()));
+                    }
+                    _ => {
+                        distances.insert(neighbor.clone(), candidate);
+                        previous.insert(neighbor.clone(), vec![(vertex.clone(), edge.clone())]);
+                        frontier.push(candidate, neighbor.clone());
+                    }
+                }
+            }
+        }
+
+        let Some(distance) = distances.get(end).copied() else {
+            return Vec::new();
+        };
+        let mut paths = Vec::new();
+        let mut stack = vec![(end.clone(), vec![end.clone()], Vec::new())];
+        while let Some((vertex, vertices, edges)) = stack.pop() {
+            if &vertex == start {
+                let mut vertices = vertices;
+                let mut edges = edges;
+                vertices.reverse();
+                edges.reverse();
+                paths.push(WeightedPath {
+                    distance,
+                    vertices,
+                    edges,
+                });
+                continue;
+            }
+            for (from, edge) in previous.get(&vertex).into_iter().flatten() {
+                let mut vertices = vertices.clone();
+                let mut edges = edges.clone();
+                vertices.push(from.clone());
+                edges.push(edge.clone());
+                stack.push((from.clone(), vertices, edges));
+            }
+        }
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Graph;
+
+    #[test]
+    fn a_star_with_inconsistent_heuristic() {
+        let mut graph = Graph::new();
+        for (from, to, weight) in [("S", "A", 1), ("A", "B", 1), ("S", "B", 3), ("B", "G", 3)] {
+            graph.add_edge(from, to, weight);
+        }
+        // Never overestimates, but drops by 4 along an edge of weight 1
+        let heuristic = |v: &&str| if *v == "A" { 4 } else { 0 };
+        let path = graph.a_star(&"S", &"G", |w| *w, heuristic).unwrap();
+        assert_eq!(path.distance, 5);
+        assert_eq!(path.vertices, vec!["S", "A", "B", "G"]);
+        assert_eq!(path.edges.iter().sum::<i32>(), path.distance);
+    }
+}
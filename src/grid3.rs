@@ -0,0 +1,172 @@
+use crate::search::Neighborhood;
+use crate::Grid;
+use itertools::Itertools;
+
+pub type Pos3 = (usize, usize, usize);
+
+#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+pub struct Grid3<T> {
+    pub state: Vec<T>,
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+}
+
+impl<T: Default + Clone> Grid3<T> {
+    pub fn new_empty(width: usize, height: usize, depth: usize) -> Grid3<T> {
+        Grid3 {
+            state: vec![T::default(); width * height * depth],
+            width,
+            height,
+            depth,
+        }
+    }
+
+    pub fn new(state: Vec<T>, width: usize, height: usize, depth: usize) -> Grid3<T> {
+        assert_eq!(
+            state.len(),
+            width * height * depth,
+            "State has the wrong size"
+        );
+        Grid3 {
+            state,
+            width,
+            height,
+            depth,
+        }
+    }
+
+    pub fn pos_to_index(&self, pos: Pos3) -> usize {
+        let (x, y, z) = pos;
+        (z * self.height + y) * self.width + x
+    }
+
+    pub fn index_to_pos(&self, idx: usize) -> Pos3 {
+        let x = idx % self.width;
+        let y = (idx / self.width) % self.height;
+        let z = idx / (self.width * self.height);
+        (x, y, z)
+    }
+
+    pub fn contains(&self, pos: Pos3) -> bool {
+        pos.0 < self.width && pos.1 < self.height && pos.2 < self.depth
+    }
+
+    pub fn at(&self, pos: Pos3) -> &T {
+        &self.state[self.pos_to_index(pos)]
+    }
+
+    pub fn mut_at(&mut self, pos: Pos3) -> &mut T {
+        let index = self.pos_to_index(pos);
+        &mut self.state[index]
+    }
+
+    pub fn get(&self, pos: Pos3) -> Option<&T> {
+        if self.contains(pos) {
+            Some(self.at(pos))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_isize(&self, pos: (isize, isize, isize)) -> Option<&T> {
+        if pos.0 >= 0 && pos.1 >= 0 && pos.2 >= 0 {
+            self.get((pos.0 as usize, pos.1 as usize, pos.2 as usize))
+        } else {
+            None
+        }
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos3> {
+        let (width, height, depth) = (self.width, self.height, self.depth);
+        (0..depth)
+            .cartesian_product(0..height)
+            .cartesian_product(0..width)
+            .map(|((z, y), x)| (x, y, z))
+    }
+
+    fn offset_positions(
+        &self,
+        pos: Pos3,
+        deltas: impl Iterator<Item = (isize, isize, isize)>,
+    ) -> Vec<Pos3> {
+        deltas
+            .map(|(dx, dy, dz)| {
+                (
+                    pos.0 as isize + dx,
+                    pos.1 as isize + dy,
+                    pos.2 as isize + dz,
+                )
+            })
+            .filter(|p| self.get_isize(*p).is_some())
+            .map(|(x, y, z)| (x as usize, y as usize, z as usize))
+            .collect_vec()
+    }
+
+    // Face neighbors
+    pub fn neighbor_positions_6(&self, pos: Pos3) -> Vec<Pos3> {
+        let deltas = [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ];
+        self.offset_positions(pos, deltas.iter().copied())
+    }
+
+    // Face, edge and corner neighbors
+    pub fn neighbor_positions_26(&self, pos: Pos3) -> Vec<Pos3> {
+        let deltas = (-1..=1)
+            .cartesian_product(-1..=1)
+            .cartesian_product(-1..=1)
+            .map(|((dx, dy), dz)| (dx, dy, dz))
+            .filter(|delta| *delta != (0, 0, 0));
+        self.offset_positions(pos, deltas)
+    }
+
+    // The z = `z` plane as a 2D grid
+    pub fn layer(&self, z: usize) -> Grid<T> {
+        let size = self.width * self.height;
+        Grid::new(
+            self.state[z * size..(z + 1) * size].to_vec(),
+            self.width,
+            self.height,
+        )
+    }
+}
+
+impl<T: Default + Clone> Neighborhood for Grid3<T> {
+    type Pos = Pos3;
+
+    fn adjacent(&self, pos: Pos3) -> Vec<Pos3> {
+        self.neighbor_positions_6(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Grid3;
+
+    #[test]
+    fn neighbor_counts_at_corners_edges_and_centre() {
+        let grid: Grid3<u8> = Grid3::new_empty(3, 3, 3);
+        assert_eq!(grid.neighbor_positions_26((0, 0, 0)).len(), 7);
+        assert_eq!(grid.neighbor_positions_6((0, 0, 0)).len(), 3);
+        assert_eq!(grid.neighbor_positions_26((1, 0, 0)).len(), 11);
+        assert_eq!(grid.neighbor_positions_26((1, 1, 1)).len(), 26);
+        assert_eq!(grid.neighbor_positions_6((1, 1, 1)).len(), 6);
+        assert_eq!(grid.neighbor_positions_26((2, 2, 2)).len(), 7);
+    }
+
+    #[test]
+    fn positions_round_trip_through_indices() {
+        let grid: Grid3<u8> = Grid3::new_empty(2, 3, 4);
+        for (idx, pos) in grid.positions().enumerate() {
+            assert_eq!(grid.pos_to_index(pos), idx);
+            assert_eq!(grid.index_to_pos(idx), pos);
+        }
+        assert_eq!(grid.layer(3).state.len(), 6);
+    }
+}
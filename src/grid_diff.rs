@@ -0,0 +1,159 @@
+use crate::{Color, Grid};
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Display;
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct CellChange<T> {
+    pub position: (usize, usize),
+    pub before: T,
+    pub after: T,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct GridDiff<T> {
+    pub changes: Vec<CellChange<T>>,
+}
+
+impl<T> GridDiff<T> {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.changes.iter().map(|change| change.position)
+    }
+}
+
+impl<T: Display> Display for GridDiff<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(
+                f,
+                "{:?}: {} -> {}",
+                change.position, change.before, change.after
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Default + Clone + PartialEq> Grid<T> {
+    fn assert_same_size(&self, other: &Grid<T>) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "Cannot diff a {}x{} grid against a {}x{} grid",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+    }
+
+    // Cells that differ between self (before) and other (after), in row-major order
+    pub fn diff(&self, other: &Grid<T>) -> GridDiff<T> {
+        self.assert_same_size(other);
+        let changes = self
+            .positions()
+            .filter(|pos| self.at(*pos) != other.at(*pos))
+            .map(|position| CellChange {
+                position,
+                before: self.at(position).clone(),
+                after: other.at(position).clone(),
+            })
+            .collect_vec();
+        GridDiff { changes }
+    }
+}
+
+impl<T: Default + Clone + PartialEq + Display> Grid<T> {
+    // Before and after next to each other, with rows that changed marked by '*'
+    pub fn diff_side_by_side(&self, other: &Grid<T>) -> String {
+        self.assert_same_size(other);
+        let mut out = String::new();
+        for y in 0..self.height {
+            let before = self.row(y).map(|t| t.to_string()).collect::<String>();
+            let after = other.row(y).map(|t| t.to_string()).collect::<String>();
+            let marker = if self.row(y).zip(other.row(y)).any(|(a, b)| a != b) {
+                '*'
+            } else {
+                ' '
+            };
+            out.push_str(&format!("{} | {} {}\n", before, after, marker));
+        }
+        out
+    }
+
+    // The after grid with changed cells highlighted using ANSI colors
+    pub fn diff_highlighted(&self, other: &Grid<T>) -> String {
+        let diff = self.diff(other);
+        other
+            .renderer()
+            .highlight(diff.positions(), Color::Red)
+            .render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CellChange, Grid};
+
+    fn before_and_after() -> (Grid<char>, Grid<char>) {
+        (
+            Grid::from_lines(&["#..", "...", ".#."], &|c| c),
+            Grid::from_lines(&["#..", "..#", "..."], &|c| c),
+        )
+    }
+
+    #[test]
+    fn diff_lists_changes_in_row_major_order() {
+        let (before, after) = before_and_after();
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.changes,
+            vec![
+                CellChange {
+                    position: (2, 1),
+                    before: '.',
+                    after: '#'
+                },
+                CellChange {
+                    position: (1, 2),
+                    before: '#',
+                    after: '.'
+                },
+            ]
+        );
+        assert_eq!(diff.to_string(), "(2, 1): . -> #\n(1, 2): # -> .\n");
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn side_by_side_marks_changed_rows() {
+        let (before, after) = before_and_after();
+        assert_eq!(
+            before.diff_side_by_side(&after),
+            "#.. | #..  \n... | ..# *\n.#. | ... *\n"
+        );
+    }
+
+    #[test]
+    fn highlighted_diff_colors_changed_cells() {
+        let (before, after) = before_and_after();
+        assert_eq!(
+            before.diff_highlighted(&after),
+            "#..\n..\x1b[41m#\x1b[0m\n.\x1b[41m.\x1b[0m.\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot diff a 3x3 grid against a 1x1 grid")]
+    fn diff_rejects_mismatched_sizes() {
+        let (before, _) = before_and_after();
+        before.diff(&Grid::from_lines(&["."], &|c| c));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{Cardinal, Graph, Grid};
+use std::collections::{HashMap, HashSet};
+
+// A run of passable cells between two junction graph vertices. Ordered by length first
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash, Default, PartialOrd, Ord)]
+pub struct Corridor {
+    pub length: usize,
+    pub turns: usize,
+}
+
+type Passable<'a, T> = Box<dyn Fn(&T) -> bool + 'a>;
+// Cells of each corridor in the graph, keyed by its two ends in both orders
+type CorridorCells = HashMap<((usize, usize), (usize, usize)), Vec<(usize, usize)>>;
+
+// Compresses a maze into an undirected graph. Vertices are junctions, dead ends and marked
+// cells such as the start and end; edges are the corridors joining them
+pub struct JunctionGraphBuilder<'a, T> {
+    grid: &'a Grid<T>,
+    passable: Passable<'a, T>,
+    marked: HashSet<(usize, usize)>,
+}
+
+impl<'a, T: Default + Clone> JunctionGraphBuilder<'a, T> {
+    pub fn new(grid: &'a Grid<T>, passable: impl Fn(&T) -> bool + 'a) -> Self {
+        JunctionGraphBuilder {
+            grid,
+            passable: Box::new(passable),
+            marked: HashSet::new(),
+        }
+    }
+
+    // Keeps these cells as vertices even in the middle of a corridor
+    pub fn mark(mut self, positions: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.marked.extend(positions);
+        self
+    }
+
+    fn is_passable(&self, pos: (usize, usize)) -> bool {
+        (self.passable)(self.grid.at(pos))
+    }
+
+    fn exits(&self, pos: (usize, usize)) -> Vec<(Cardinal, (usize, usize))> {
+        Cardinal::ALL
+            .iter()
+            .filter_map(|cardinal| {
+                self.grid
+                    .get_neighbor_position(pos, *cardinal)
+                    .filter(|neighbor| self.is_passable(*neighbor))
+                    .map(|neighbor| (*cardinal, neighbor))
+            })
+            .collect()
+    }
+
+    fn is_vertex(&self, pos: (usize, usize)) -> bool {
+        self.marked.contains(&pos) || self.exits(pos).len() != 2
+    }
+
+    // Cells of the corridor from `start` heading into `first`, up to and including the next vertex
+    fn follow(
+        &self,
+        start: (usize, usize),
+        cardinal: Cardinal,
+        first: (usize, usize),
+    ) -> Vec<(usize, usize)> {
+        let mut cells = vec![start, first];
+        let (mut pos, mut heading) = (first, cardinal);
+        while pos != start && !self.is_vertex(pos) {
+            let (next_heading, next) = self
+                .exits(pos)
+                .into_iter()
+                .find(|(c, _)| *c != heading.opposite())
+                .unwrap();
+            cells.push(next);
+            pos = next;
+            heading = next_heading;
+        }
+        cells
+    }
+
+    // Where two corridors join the same pair of vertices, one of them is split in the middle
+    // by an extra vertex, so that searches for long paths can still take either
+    pub fn build(&self) -> Graph<(usize, usize), Corridor> {
+        let mut graph = Graph::new_undirected();
+        let mut corridors = CorridorCells::new();
+        // Exits already walked from the other end, as (vertex, first cell)
+        let mut walked = HashSet::new();
+        let vertices = self
+            .grid
+            .positions()
+            .filter(|pos| self.is_passable(*pos) && self.is_vertex(*pos))
+            .collect::<Vec<_>>();
+        for vertex in vertices {
+            graph.add_vertex(vertex);
+            for (cardinal, first) in self.exits(vertex) {
+                if walked.contains(&(vertex, first)) {
+                    continue;
+                }
+                let cells = self.follow(vertex, cardinal, first);
+                let end = *cells.last().unwrap();
+                if end == vertex {
+                    continue;
+                }
+                walked.insert((end, cells[cells.len() - 2]));
+                match corridors.remove(&(vertex, end)) {
+                    None => add_corridor(&mut graph, &mut corridors, cells),
+                    Some(existing) => {
+                        // A corridor of length 1 has no middle cell, so split the other one
+                        let (kept, split) = if cells.len() > existing.len() {
+                            (existing, cells)
+                        } else {
+                            (cells, existing)
+                        };
+                        graph.remove_edge(&vertex, &end);
+                        add_corridor(&mut graph, &mut corridors, kept);
+                        let middle = split.len() / 2;
+                        add_corridor(&mut graph, &mut corridors, split[..=middle].to_vec());
+                        add_corridor(&mut graph, &mut corridors, split[middle..].to_vec());
+                    }
+                }
+            }
+        }
+        graph
+    }
+}
+
+fn add_corridor(
+    graph: &mut Graph<(usize, usize), Corridor>,
+    corridors: &mut CorridorCells,
+    cells: Vec<(usize, usize)>,
+) {
+    let (start, end) = (cells[0], cells[cells.len() - 1]);
+    // A turn is any change of direction at a cell between the two ends
+    let turns = cells
+        .windows(3)
+        .filter(|w| {
+            let first = (
+                w[1].0 as isize - w[0].0 as isize,
+                w[1].1 as isize - w[0].1 as isize,
+            );
+            let second = (
+                w[2].0 as isize - w[1].0 as isize,
+                w[2].1 as isize - w[1].1 as isize,
+            );
+            first != second
+        })
+        .count();
+    let corridor = Corridor {
+        length: cells.len() - 1,
+        turns,
+    };
+    graph.add_edge(start, end, corridor);
+    let mut reversed = cells.clone();
+    reversed.reverse();
+    corridors.insert((end, start), reversed);
+    corridors.insert((start, end), cells);
+}
+
+impl<T: Default + Clone> Grid<T> {
+    pub fn junction_graph<'a>(
+        &'a self,
+        passable: impl Fn(&T) -> bool + 'a,
+    ) -> JunctionGraphBuilder<'a, T> {
+        JunctionGraphBuilder::new(self, passable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Grid;
+
+    #[test]
+    fn parallel_corridors_are_both_kept() {
+        let grid = Grid::from_lines(&["#S...E#", "#.###.#", "#.....#"], &|c| c);
+        let graph = grid
+            .junction_graph(|c| *c != '#')
+            .mark([(1, 0), (5, 0)])
+            .build();
+        let longest = graph.longest_path(&(1, 0), &(5, 0), |c| c.length).unwrap();
+        assert_eq!(longest.distance, 8);
+        let shortest = graph.dijkstra(&(1, 0), &(5, 0), |c| c.length).unwrap();
+        assert_eq!(shortest.distance, 4);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::Grid;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct ParsedGrid<T> {
+    pub grid: Grid<T>,
+    pub markers: HashMap<char, Vec<(usize, usize)>>,
+}
+
+impl<T> ParsedGrid<T> {
+    // Position of a marker that is expected to appear exactly once
+    pub fn marker(&self, c: char) -> Option<(usize, usize)> {
+        match self.markers.get(&c).map(|positions| positions.as_slice()) {
+            Some([pos]) => Some(*pos),
+            _ => None,
+        }
+    }
+
+    pub fn marker_positions(&self, c: char) -> &[(usize, usize)] {
+        self.markers
+            .get(&c)
+            .map(|positions| positions.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+// Splits input on blank lines, e.g. a map followed by a list of moves
+pub fn split_sections<'a, 'b>(lines: &'a [&'b str]) -> Vec<&'a [&'b str]> {
+    lines
+        .split(|line| line.trim().is_empty())
+        .filter(|section| !section.is_empty())
+        .collect_vec()
+}
+
+impl<T: Default + Clone> Grid<T> {
+    // Like from_lines, but reports non-rectangular input instead of building a skewed grid
+    pub fn try_from_lines(
+        lines: &[&str],
+        transformer: &dyn Fn(char) -> T,
+    ) -> Result<Grid<T>, String> {
+        let width = lines
+            .first()
+            .ok_or_else(|| "Cannot build a grid from no lines".to_string())?
+            .chars()
+            .count();
+        if let Some((y, line)) = lines
+            .iter()
+            .enumerate()
+            .find(|(_, line)| line.chars().count() != width)
+        {
+            return Err(format!(
+                "Line {} has width {}, expected {} (from line 0): {:?}",
+                y,
+                line.chars().count(),
+                width,
+                line
+            ));
+        }
+        let state = lines
+            .iter()
+            .flat_map(|line| line.chars().map(transformer))
+            .collect_vec();
+        Ok(Grid::new(state, width, lines.len()))
+    }
+
+    // Accepts lines of different lengths, filling the short ones out with `padding`
+    pub fn from_ragged_lines(
+        lines: &[&str],
+        padding: T,
+        transformer: &dyn Fn(char) -> T,
+    ) -> Grid<T> {
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let state = lines
+            .iter()
+            .flat_map(|line| {
+                let len = line.chars().count();
+                line.chars()
+                    .map(transformer)
+                    .chain(std::iter::repeat_n(padding.clone(), width - len))
+            })
+            .collect_vec();
+        Grid::new(state, width, lines.len())
+    }
+
+    // Parses a rectangular grid, recording where each of the `markers` characters appeared.
+    // Marker cells still go through `transformer`, so it decides what they are replaced with
+    pub fn from_lines_with_markers(
+        lines: &[&str],
+        markers: &str,
+        transformer: &dyn Fn(char) -> T,
+    ) -> Result<ParsedGrid<T>, String> {
+        let grid = Grid::try_from_lines(lines, transformer)?;
+        let mut found: HashMap<char, Vec<(usize, usize)>> = HashMap::new();
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if markers.contains(c) {
+                    found.entry(c).or_default().push((x, y));
+                }
+            }
+        }
+        Ok(ParsedGrid {
+            grid,
+            markers: found,
+        })
+    }
+
+    // One grid per blank-line separated section, as in stacked key/lock schematics
+    pub fn from_sections(
+        lines: &[&str],
+        transformer: &dyn Fn(char) -> T,
+    ) -> Result<Vec<Grid<T>>, String> {
+        split_sections(lines)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, section)| {
+                Grid::try_from_lines(section, transformer)
+                    .map_err(|e| format!("Section {idx}: {e}"))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Grid;
+
+    #[test]
+    fn width_counts_characters_not_bytes() {
+        let grid = Grid::try_from_lines(&["→←", "↑↓"], &|c| c).unwrap();
+        assert_eq!((grid.width, grid.height), (2, 2));
+        assert_eq!(*grid.at((1, 1)), '↓');
+        assert!(Grid::try_from_lines(&["→←", "↑"], &|c| c).is_err());
+    }
+}
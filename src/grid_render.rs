@@ -0,0 +1,285 @@
+use crate::{Cardinal, Grid};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+use std::io::Write;
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 49, 49),
+            Color::Green => (13, 188, 121),
+            Color::Yellow => (229, 229, 16),
+            Color::Blue => (36, 114, 200),
+            Color::Magenta => (188, 63, 188),
+            Color::Cyan => (17, 168, 205),
+            Color::White => (229, 229, 229),
+            Color::Rgb(r, g, b) => (*r, *g, *b),
+        }
+    }
+
+    fn ansi_code(&self, background: bool) -> String {
+        let base = if background { 40 } else { 30 };
+        match self {
+            Color::Black => format!("{}", base),
+            Color::Red => format!("{}", base + 1),
+            Color::Green => format!("{}", base + 2),
+            Color::Yellow => format!("{}", base + 3),
+            Color::Blue => format!("{}", base + 4),
+            Color::Magenta => format!("{}", base + 5),
+            Color::Cyan => format!("{}", base + 6),
+            Color::White => format!("{}", base + 7),
+            Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+        }
+    }
+
+    pub fn paint(&self, text: &str) -> String {
+        format!("\x1b[{}m{}\x1b[0m", self.ansi_code(false), text)
+    }
+
+    pub fn paint_background(&self, text: &str) -> String {
+        format!("\x1b[{}m{}\x1b[0m", self.ansi_code(true), text)
+    }
+}
+
+type Colorizer<'a, T> = Box<dyn Fn(&T) -> Option<Color> + 'a>;
+
+// Builds up a textual picture of a grid with paths, highlights and rulers layered on top
+pub struct GridRenderer<'a, T> {
+    grid: &'a Grid<T>,
+    overlays: HashMap<(usize, usize), char>,
+    highlights: HashMap<(usize, usize), Color>,
+    colorizer: Option<Colorizer<'a, T>>,
+    rulers: bool,
+}
+
+impl<'a, T: Display + Default + Clone> GridRenderer<'a, T> {
+    pub fn new(grid: &'a Grid<T>) -> GridRenderer<'a, T> {
+        GridRenderer {
+            grid,
+            overlays: HashMap::new(),
+            highlights: HashMap::new(),
+            colorizer: None,
+            rulers: false,
+        }
+    }
+
+    // Draws `c` over every given position
+    pub fn overlay(mut self, positions: impl IntoIterator<Item = (usize, usize)>, c: char) -> Self {
+        for pos in positions {
+            self.overlays.insert(pos, c);
+        }
+        self
+    }
+
+    // Draws an arrow on each step of the path pointing at the next position.
+    // Non-adjacent steps are drawn as '*', and the final cell is left as is
+    pub fn path(mut self, path: &[(usize, usize)]) -> Self {
+        for (from, to) in path.iter().zip(path.iter().skip(1)) {
+            let delta = (
+                to.0 as isize - from.0 as isize,
+                to.1 as isize - from.1 as isize,
+            );
+            let c = Cardinal::from_delta(delta)
+                .map(|cardinal| cardinal.to_char())
+                .unwrap_or('*');
+            self.overlays.insert(*from, c);
+        }
+        self
+    }
+
+    pub fn highlight(
+        mut self,
+        positions: impl IntoIterator<Item = (usize, usize)>,
+        color: Color,
+    ) -> Self {
+        for pos in positions {
+            self.highlights.insert(pos, color);
+        }
+        self
+    }
+
+    // Foreground color per cell value, or None to leave it uncolored
+    pub fn colors(mut self, colorizer: impl Fn(&T) -> Option<Color> + 'a) -> Self {
+        self.colorizer = Some(Box::new(colorizer));
+        self
+    }
+
+    pub fn rulers(mut self) -> Self {
+        self.rulers = true;
+        self
+    }
+
+    pub fn render(&self) -> String {
+        self.to_string()
+    }
+
+    fn cell(&self, pos: (usize, usize)) -> String {
+        let value = self.grid.at(pos);
+        let mut text = match self.overlays.get(&pos) {
+            Some(c) => c.to_string(),
+            None => value.to_string(),
+        };
+        if let Some(color) = self.colorizer.as_ref().and_then(|f| f(value)) {
+            text = color.paint(&text);
+        }
+        if let Some(color) = self.highlights.get(&pos) {
+            text = color.paint_background(&text);
+        }
+        text
+    }
+}
+
+impl<T: Display + Default + Clone> Display for GridRenderer<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let margin = if self.rulers {
+            (self.grid.height.max(1) - 1).to_string().len() + 1
+        } else {
+            0
+        };
+        if self.rulers {
+            let digits = (self.grid.width.max(1) - 1).to_string().len();
+            for place in (0..digits).rev() {
+                write!(f, "{:margin$}", "")?;
+                for x in 0..self.grid.width {
+                    let magnitude = 10usize.pow(place as u32);
+                    if place == 0 || x >= magnitude {
+                        write!(f, "{}", (x / magnitude) % 10)?;
+                    } else {
+                        write!(f, " ")?;
+                    }
+                }
+                writeln!(f)?;
+            }
+        }
+        for y in 0..self.grid.height {
+            if self.rulers {
+                write!(f, "{:>width$} ", y, width = margin - 1)?;
+            }
+            for x in 0..self.grid.width {
+                write!(f, "{}", self.cell((x, y)))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Display + Default + Clone> Grid<T> {
+    pub fn renderer(&self) -> GridRenderer<'_, T> {
+        GridRenderer::new(self)
+    }
+}
+
+impl<T: Default + Clone> Grid<T> {
+    // Plain (P1) portable bitmap, one pixel per cell
+    pub fn write_pbm(&self, out: &mut impl Write, is_set: &dyn Fn(&T) -> bool) -> io::Result<()> {
+        writeln!(out, "P1")?;
+        writeln!(out, "{} {}", self.width, self.height)?;
+        for row in self.state.chunks(self.width.max(1)) {
+            let line = row
+                .iter()
+                .map(|t| if is_set(t) { "1" } else { "0" })
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(out, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    // Plain (P3) portable pixmap, one pixel per cell
+    pub fn write_ppm(&self, out: &mut impl Write, color: &dyn Fn(&T) -> Color) -> io::Result<()> {
+        writeln!(out, "P3")?;
+        writeln!(out, "{} {}", self.width, self.height)?;
+        writeln!(out, "255")?;
+        for row in self.state.chunks(self.width.max(1)) {
+            let line = row
+                .iter()
+                .map(|t| {
+                    let (r, g, b) = color(t).to_rgb();
+                    format!("{} {} {}", r, g, b)
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(out, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, Grid};
+
+    #[test]
+    fn rulers_label_every_column_and_row() {
+        let grid = Grid::new(vec!['.'; 12 * 11], 12, 11);
+        let rendered = grid
+            .renderer()
+            .rulers()
+            .path(&[(0, 0), (1, 0), (1, 1), (3, 1)])
+            .render();
+        let mut expected = vec![
+            "             11".to_string(),
+            "   012345678901".to_string(),
+            " 0 >v..........".to_string(),
+            " 1 .*..........".to_string(),
+        ];
+        expected.extend((2..11).map(|y| format!("{:>2} ............", y)));
+        assert_eq!(rendered, expected.join("\n") + "\n");
+    }
+
+    #[test]
+    fn colors_and_highlights_wrap_cells_in_escape_codes() {
+        let grid = Grid::from_lines(&["#."], &|c| c);
+        let rendered = grid
+            .renderer()
+            .colors(|c| if *c == '#' { Some(Color::Red) } else { None })
+            .highlight([(1, 0)], Color::Rgb(1, 2, 3))
+            .render();
+        assert_eq!(rendered, "\x1b[31m#\x1b[0m\x1b[48;2;1;2;3m.\x1b[0m\n");
+    }
+
+    #[test]
+    fn writes_plain_bitmaps_and_pixmaps() {
+        let grid = Grid::from_lines(&["#..", ".#."], &|c| c);
+        let mut pbm = Vec::new();
+        grid.write_pbm(&mut pbm, &|c| *c == '#').unwrap();
+        assert_eq!(String::from_utf8(pbm).unwrap(), "P1\n3 2\n1 0 0\n0 1 0\n");
+
+        let mut ppm = Vec::new();
+        grid.write_ppm(&mut ppm, &|c| {
+            if *c == '#' {
+                Color::Rgb(255, 0, 0)
+            } else {
+                Color::Black
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(ppm).unwrap(),
+            "P3\n3 2\n255\n255 0 0 0 0 0 0 0 0\n0 0 0 255 0 0 0 0 0\n"
+        );
+
+        let mut empty = Vec::new();
+        Grid::<char>::new(vec![], 0, 0)
+            .write_pbm(&mut empty, &|_| true)
+            .unwrap();
+        assert_eq!(String::from_utf8(empty).unwrap(), "P1\n0 0\n");
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{Grid, Octant};
+use itertools::Itertools;
+
+// Orientation of a pattern relative to how it was given: `rotations` clockwise
+// quarter turns, followed by a left-right mirror if `flipped`
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct Orientation {
+    pub rotations: usize,
+    pub flipped: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct PatternMatch {
+    // Top left corner of the (oriented) pattern
+    pub position: (usize, usize),
+    pub orientation: Orientation,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct SequenceMatch {
+    pub start: (usize, usize),
+    pub direction: Octant,
+}
+
+impl<T: Default + Clone + PartialEq> Grid<T> {
+    fn pattern_matches_at(&self, pattern: &Grid<Option<T>>, pos: (usize, usize)) -> bool {
+        pattern.positions().all(|(x, y)| match pattern.at((x, y)) {
+            Some(expected) => self.at((pos.0 + x, pos.1 + y)) == expected,
+            None => true,
+        })
+    }
+
+    // Top left corners of every placement of `pattern`, where `None` cells match anything.
+    // An empty pattern matches nowhere
+    pub fn find_pattern(&self, pattern: &Grid<Option<T>>) -> Vec<(usize, usize)> {
+        if pattern.state.is_empty() || pattern.width > self.width || pattern.height > self.height {
+            return Vec::new();
+        }
+        (0..=self.height - pattern.height)
+            .cartesian_product(0..=self.width - pattern.width)
+            .map(|(y, x)| (x, y))
+            .filter(|pos| self.pattern_matches_at(pattern, *pos))
+            .collect_vec()
+    }
+
+    // Like find_pattern, but also tries rotated and/or mirrored copies of the pattern.
+    // Orientations that produce an identical template are only searched once
+    pub fn find_pattern_oriented(
+        &self,
+        pattern: &Grid<Option<T>>,
+        rotations: bool,
+        reflections: bool,
+    ) -> Vec<PatternMatch> {
+        if pattern.state.is_empty() {
+            return Vec::new();
+        }
+        let mut templates: Vec<(Orientation, Grid<Option<T>>)> = Vec::new();
+        let mut current = pattern.clone();
+        for rotation in 0..if rotations { 4 } else { 1 } {
+            let mut candidates = vec![(false, current.clone())];
+            if reflections {
+                candidates.push((true, current.flip_horizontal()));
+            }
+            for (flipped, template) in candidates {
+                if !templates.iter().any(|(_, t)| *t == template) {
+                    let orientation = Orientation {
+                        rotations: rotation,
+                        flipped,
+                    };
+                    templates.push((orientation, template));
+                }
+            }
+            current = current.rotate_clockwise();
+        }
+
+        templates
+            .iter()
+            .flat_map(|(orientation, template)| {
+                self.find_pattern(template)
+                    .into_iter()
+                    .map(move |position| PatternMatch {
+                        position,
+                        orientation: *orientation,
+                    })
+            })
+            .collect_vec()
+    }
+
+    pub fn sequence_matches_at(
+        &self,
+        sequence: &[T],
+        start: (usize, usize),
+        direction: Octant,
+    ) -> bool {
+        let direction = direction.delta();
+        sequence.iter().enumerate().all(|(i, expected)| {
+            let pos = (
+                start.0 as isize + direction.0 * i as isize,
+                start.1 as isize + direction.1 * i as isize,
+            );
+            self.get_isize(pos) == Some(expected)
+        })
+    }
+
+    // Every start position and direction along which `sequence` can be read. An empty
+    // sequence matches nowhere
+    pub fn find_sequence(&self, sequence: &[T], directions: &[Octant]) -> Vec<SequenceMatch> {
+        if sequence.is_empty() {
+            return Vec::new();
+        }
+        self.positions()
+            .cartesian_product(directions.iter())
+            .filter(|(start, direction)| self.sequence_matches_at(sequence, *start, **direction))
+            .map(|(start, direction)| SequenceMatch {
+                start,
+                direction: *direction,
+            })
+            .collect_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grid, Octant, Orientation};
+
+    fn pattern(lines: &[&str]) -> Grid<Option<char>> {
+        Grid::from_lines(lines, &|c| if c == '.' { None } else { Some(c) })
+    }
+
+    #[test]
+    fn finds_xmas_in_every_direction() {
+        let lines = include_str!("../samples/4.txt").lines().collect::<Vec<_>>();
+        let grid = Grid::from_lines(&lines, &|c| c);
+        let xmas = "XMAS".chars().collect::<Vec<_>>();
+        let matches = grid.find_sequence(&xmas, &Octant::ALL);
+        assert_eq!(matches.len(), 18);
+        assert_eq!(
+            matches
+                .iter()
+                .filter(|m| m.direction == Octant::East)
+                .count(),
+            3
+        );
+        assert_eq!(grid.find_sequence(&xmas, &Octant::ORTHOGONAL).len(), 8);
+    }
+
+    #[test]
+    fn finds_rotated_and_reflected_patterns() {
+        let grid = Grid::from_lines(&["ab", "ba"], &|c| c);
+        let ab = pattern(&["ab"]);
+        assert_eq!(grid.find_pattern(&ab), vec![(0, 0)]);
+        // Each quarter turn of "ab" appears once, and reflections add nothing new
+        assert_eq!(grid.find_pattern_oriented(&ab, true, false).len(), 4);
+        assert_eq!(grid.find_pattern_oriented(&ab, true, true).len(), 4);
+
+        let mirrored = grid.find_pattern_oriented(&ab, false, true);
+        assert_eq!(mirrored.len(), 2);
+        let flipped = mirrored.iter().find(|m| m.orientation.flipped).unwrap();
+        assert_eq!(flipped.position, (0, 1));
+        assert_eq!(
+            flipped.orientation,
+            Orientation {
+                rotations: 0,
+                flipped: true
+            }
+        );
+    }
+
+    #[test]
+    fn empty_patterns_match_nowhere() {
+        let grid = Grid::from_lines(&["ab", "ba"], &|c| c);
+        assert!(grid.find_sequence(&[], &Octant::ALL).is_empty());
+        assert!(grid.find_pattern(&Grid::new(vec![], 0, 0)).is_empty());
+        assert!(grid
+            .find_pattern_oriented(&Grid::new(vec![], 0, 0), true, true)
+            .is_empty());
+    }
+}
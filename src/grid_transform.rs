@@ -0,0 +1,245 @@
+use crate::Grid;
+use itertools::Itertools;
+
+impl<T: Default + Clone> Grid<T> {
+    fn from_fn(width: usize, height: usize, f: impl Fn((usize, usize)) -> T) -> Grid<T> {
+        let state = (0..height)
+            .cartesian_product(0..width)
+            .map(|(y, x)| f((x, y)))
+            .collect_vec();
+        Grid::new(state, width, height)
+    }
+
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |(x, y)| self.at((y, x)).clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self.at((y, self.height - 1 - x)).clone()
+        })
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self.at((self.width - 1 - y, x)).clone()
+        })
+    }
+
+    pub fn rotate_180(&self) -> Grid<T> {
+        let mut rotated = self.clone();
+        rotated.rotate_180_mut();
+        rotated
+    }
+
+    // Mirror left to right
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let mut flipped = self.clone();
+        flipped.flip_horizontal_mut();
+        flipped
+    }
+
+    // Mirror top to bottom
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let mut flipped = self.clone();
+        flipped.flip_vertical_mut();
+        flipped
+    }
+
+    pub fn transpose_mut(&mut self) {
+        *self = self.transpose();
+    }
+
+    pub fn rotate_clockwise_mut(&mut self) {
+        *self = self.rotate_clockwise();
+    }
+
+    pub fn rotate_counter_clockwise_mut(&mut self) {
+        *self = self.rotate_counter_clockwise();
+    }
+
+    pub fn rotate_180_mut(&mut self) {
+        self.state.reverse();
+    }
+
+    pub fn flip_horizontal_mut(&mut self) {
+        // chunks_mut panics on a zero width, and there is nothing to mirror anyway
+        if self.width == 0 {
+            return;
+        }
+        for row in self.state.chunks_mut(self.width) {
+            row.reverse();
+        }
+    }
+
+    pub fn flip_vertical_mut(&mut self) {
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.state.split_at_mut((self.height - 1 - y) * self.width);
+            top[y * self.width..(y + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+
+    // All eight rotations and reflections, starting with the identity
+    pub fn orientations(&self) -> Vec<Grid<T>> {
+        let mut orientations = Vec::with_capacity(8);
+        let mut current = self.clone();
+        for _ in 0..4 {
+            orientations.push(current.clone());
+            orientations.push(current.flip_horizontal());
+            current = current.rotate_clockwise();
+        }
+        orientations
+    }
+
+    // Every cell becomes an x_factor by y_factor block of copies of itself
+    pub fn scale(&self, x_factor: usize, y_factor: usize) -> Grid<T> {
+        self.scale_with(x_factor, y_factor, &|t| {
+            vec![t.clone(); x_factor * y_factor]
+        })
+    }
+
+    // Every cell becomes an x_factor by y_factor block, given in row-major order by `expander`
+    pub fn scale_with(
+        &self,
+        x_factor: usize,
+        y_factor: usize,
+        expander: &dyn Fn(&T) -> Vec<T>,
+    ) -> Grid<T> {
+        let blocks = self.state.iter().map(expander).collect_vec();
+        for block in &blocks {
+            assert_eq!(
+                block.len(),
+                x_factor * y_factor,
+                "Expanded block has the wrong size"
+            );
+        }
+        let width = self.width * x_factor;
+        let height = self.height * y_factor;
+        Grid::from_fn(width, height, |(x, y)| {
+            let block = &blocks[self.pos_to_index((x / x_factor, y / y_factor))];
+            block[(y % y_factor) * x_factor + x % x_factor].clone()
+        })
+    }
+
+    // Repeat the grid x_count times horizontally and y_count times vertically
+    pub fn tile(&self, x_count: usize, y_count: usize) -> Grid<T> {
+        Grid::from_fn(self.width * x_count, self.height * y_count, |(x, y)| {
+            self.at((x % self.width, y % self.height)).clone()
+        })
+    }
+
+    // Joins rows of grids into a single grid. Grids in the same row must share a height,
+    // and every row must add up to the same width
+    pub fn stitch(tiles: &[Vec<Grid<T>>]) -> Grid<T> {
+        let width = tiles
+            .first()
+            .map(|row| row.iter().map(|tile| tile.width).sum())
+            .unwrap_or(0);
+        let mut state = Vec::new();
+        let mut height = 0;
+        for row in tiles {
+            let row_height = row.first().map(|tile| tile.height).unwrap_or(0);
+            assert!(
+                row.iter().all(|tile| tile.height == row_height),
+                "Tiles in a row must have the same height"
+            );
+            assert_eq!(
+                row.iter().map(|tile| tile.width).sum::<usize>(),
+                width,
+                "Rows of tiles must have the same width"
+            );
+            for y in 0..row_height {
+                for tile in row {
+                    state.extend(tile.row(y).cloned());
+                }
+            }
+            height += row_height;
+        }
+        Grid::new(state, width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Grid;
+
+    fn sample() -> Grid<char> {
+        Grid::from_lines(&["abc", "def"], &|c| c)
+    }
+
+    #[test]
+    fn rotate_clockwise() {
+        let rotated = sample().rotate_clockwise();
+        assert_eq!(rotated, Grid::from_lines(&["da", "eb", "fc"], &|c| c));
+    }
+
+    #[test]
+    fn four_rotations_are_identity() {
+        let grid = sample();
+        let mut rotated = grid.clone();
+        for _ in 0..4 {
+            rotated.rotate_clockwise_mut();
+        }
+        assert_eq!(rotated, grid);
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+        assert_eq!(
+            grid.rotate_clockwise().rotate_clockwise(),
+            grid.rotate_180()
+        );
+    }
+
+    #[test]
+    fn transpose_and_flips_are_involutions() {
+        let grid = sample();
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(grid.flip_horizontal().flip_horizontal(), grid);
+        assert_eq!(grid.flip_vertical().flip_vertical(), grid);
+        assert_eq!(grid.flip_horizontal().flip_vertical(), grid.rotate_180());
+        assert_eq!(grid.transpose().flip_horizontal(), grid.rotate_clockwise());
+    }
+
+    #[test]
+    fn orientations_are_distinct() {
+        let grid = sample();
+        let orientations = grid.orientations();
+        assert_eq!(orientations[0], grid);
+        for (i, a) in orientations.iter().enumerate() {
+            for b in orientations.iter().skip(i + 1) {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn scale_and_tile() {
+        let grid = sample();
+        let scaled = grid.scale(2, 1);
+        assert_eq!(scaled, Grid::from_lines(&["aabbcc", "ddeeff"], &|c| c));
+
+        let expanded = grid.scale_with(1, 2, &|c| vec![*c, c.to_ascii_uppercase()]);
+        assert_eq!(
+            expanded,
+            Grid::from_lines(&["abc", "ABC", "def", "DEF"], &|c| c)
+        );
+
+        let tiled = grid.tile(2, 2);
+        assert_eq!(
+            tiled,
+            Grid::stitch(&[vec![grid.clone(), grid.clone()], vec![grid.clone(), grid]])
+        );
+        assert_eq!(tiled.get_subgrid((3, 2), 3, 2), sample());
+    }
+
+    #[test]
+    fn empty_grids_transform_to_empty_grids() {
+        for grid in [Grid::<char>::new(vec![], 0, 0), Grid::new(vec![], 0, 3)] {
+            assert_eq!(grid.flip_horizontal(), grid);
+            assert_eq!(grid.flip_vertical(), grid);
+            assert_eq!(grid.rotate_180(), grid);
+            assert_eq!(grid.transpose().state.len(), 0);
+            assert_eq!(grid.rotate_clockwise().state.len(), 0);
+            assert_eq!(grid.orientations().len(), 8);
+            assert_eq!(grid.scale(2, 2).state.len(), 0);
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use crate::search::Neighborhood;
+use std::collections::HashMap;
+use std::ops::Add;
+use std::str::FromStr;
+
+// Directions on a pointy-topped hex grid, where rows are offset and east/west are neighbors
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    fn index(&self) -> usize {
+        HexDirection::ALL.iter().position(|d| d == self).unwrap()
+    }
+
+    // Axial (q, r) offset of one step in this direction
+    pub fn delta(&self) -> Hex {
+        match self {
+            HexDirection::East => Hex::new(1, 0),
+            HexDirection::NorthEast => Hex::new(1, -1),
+            HexDirection::NorthWest => Hex::new(0, -1),
+            HexDirection::West => Hex::new(-1, 0),
+            HexDirection::SouthWest => Hex::new(-1, 1),
+            HexDirection::SouthEast => Hex::new(0, 1),
+        }
+    }
+
+    pub fn opposite(&self) -> HexDirection {
+        HexDirection::ALL[(self.index() + 3) % 6]
+    }
+
+    pub fn clockwise(&self) -> HexDirection {
+        HexDirection::ALL[(self.index() + 5) % 6]
+    }
+
+    pub fn counter_clockwise(&self) -> HexDirection {
+        HexDirection::ALL[(self.index() + 1) % 6]
+    }
+
+    // Splits a run of directions with no separators, like "esenee"
+    pub fn parse_run(s: &str) -> Result<Vec<HexDirection>, String> {
+        let mut directions = Vec::new();
+        let mut rest = s.trim();
+        while !rest.is_empty() {
+            let len = if rest.starts_with('n') || rest.starts_with('s') {
+                2
+            } else {
+                1
+            };
+            let token = rest
+                .get(..len)
+                .ok_or_else(|| format!("Unknown hex direction {rest}"))?;
+            directions.push(token.parse()?);
+            rest = &rest[len..];
+        }
+        Ok(directions)
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "e" => Ok(HexDirection::East),
+            "ne" => Ok(HexDirection::NorthEast),
+            "nw" => Ok(HexDirection::NorthWest),
+            "w" => Ok(HexDirection::West),
+            "sw" => Ok(HexDirection::SouthWest),
+            "se" => Ok(HexDirection::SouthEast),
+            _ => Err(format!("Unknown hex direction {s}")),
+        }
+    }
+}
+
+// Axial hex coordinate. The implied third cube coordinate is s = -q - r
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+impl Hex {
+    pub fn new(q: isize, r: isize) -> Hex {
+        Hex { q, r }
+    }
+
+    pub fn from_cube(x: isize, y: isize, z: isize) -> Hex {
+        assert_eq!(x + y + z, 0, "Cube coordinates must sum to zero");
+        Hex { q: x, r: z }
+    }
+
+    // (q, s, r) ordering so that x + y + z == 0
+    pub fn to_cube(&self) -> (isize, isize, isize) {
+        (self.q, self.s(), self.r)
+    }
+
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn neighbor(&self, direction: HexDirection) -> Hex {
+        *self + direction.delta()
+    }
+
+    pub fn neighbors(&self) -> [Hex; 6] {
+        HexDirection::ALL.map(|direction| self.neighbor(direction))
+    }
+
+    pub fn distance(&self, other: &Hex) -> usize {
+        let (dq, dr, ds) = (self.q - other.q, self.r - other.r, self.s() - other.s());
+        dq.unsigned_abs()
+            .max(dr.unsigned_abs())
+            .max(ds.unsigned_abs())
+    }
+
+    // Hexes within `radius` steps, including this one
+    pub fn within(&self, radius: usize) -> Vec<Hex> {
+        let radius = radius as isize;
+        (-radius..=radius)
+            .flat_map(|dq| {
+                let low = (-radius).max(-dq - radius);
+                let high = radius.min(-dq + radius);
+                (low..=high).map(move |dr| Hex::new(dq, dr))
+            })
+            .map(|delta| *self + delta)
+            .collect()
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+// Sparse hex grid. Cells that were never written read as `default`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct HexGrid<T> {
+    pub cells: HashMap<Hex, T>,
+    pub default: T,
+}
+
+impl<T: Clone> HexGrid<T> {
+    pub fn new(default: T) -> HexGrid<T> {
+        HexGrid {
+            cells: HashMap::new(),
+            default,
+        }
+    }
+
+    // Every hex within `radius` of the origin, filled with `default`
+    pub fn with_radius(radius: usize, default: T) -> HexGrid<T> {
+        let cells = Hex::default()
+            .within(radius)
+            .into_iter()
+            .map(|hex| (hex, default.clone()))
+            .collect();
+        HexGrid { cells, default }
+    }
+
+    pub fn at(&self, hex: Hex) -> &T {
+        self.cells.get(&hex).unwrap_or(&self.default)
+    }
+
+    pub fn get(&self, hex: Hex) -> Option<&T> {
+        self.cells.get(&hex)
+    }
+
+    pub fn set(&mut self, hex: Hex, value: T) -> Option<T> {
+        self.cells.insert(hex, value)
+    }
+
+    pub fn mut_at(&mut self, hex: Hex) -> &mut T {
+        let default = &self.default;
+        self.cells.entry(hex).or_insert_with(|| default.clone())
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Hex> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn neighbors(&self, hex: Hex) -> impl Iterator<Item = &T> {
+        IntoIterator::into_iter(hex.neighbors()).map(move |n| self.at(n))
+    }
+}
+
+impl<T: Clone> Neighborhood for HexGrid<T> {
+    type Pos = Hex;
+
+    // Only hexes that are stored in the grid, so searches stay bounded
+    fn adjacent(&self, hex: Hex) -> Vec<Hex> {
+        hex.neighbors()
+            .iter()
+            .copied()
+            .filter(|n| self.cells.contains_key(n))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Hex, HexDirection};
+
+    #[test]
+    fn within_counts_centred_hexagonal_numbers() {
+        for radius in 0..6 {
+            let hexes = Hex::new(2, -3).within(radius);
+            assert_eq!(hexes.len(), 3 * radius * radius + 3 * radius + 1);
+            assert!(hexes
+                .iter()
+                .all(|hex| hex.distance(&Hex::new(2, -3)) <= radius));
+        }
+    }
+
+    #[test]
+    fn parse_run_loops_back_to_the_origin() {
+        let run = HexDirection::parse_run("nwwswee").unwrap();
+        assert_eq!(run.len(), 5);
+        let end = run
+            .iter()
+            .fold(Hex::default(), |hex, direction| hex.neighbor(*direction));
+        assert_eq!(end, Hex::default());
+        assert!(HexDirection::parse_run("nx").is_err());
+        assert!(HexDirection::parse_run("n").is_err());
+    }
+
+    #[test]
+    fn directions_turn_and_reverse() {
+        for direction in HexDirection::ALL.iter() {
+            assert_eq!(direction.opposite().opposite(), *direction);
+            assert_eq!(direction.clockwise().counter_clockwise(), *direction);
+            assert_eq!(
+                Hex::default()
+                    .neighbor(*direction)
+                    .distance(&Hex::default()),
+                1
+            );
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::search::Neighborhood;
+use crate::{Cardinal, Grid};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+
+// Sparse, unbounded grid. Cells that were never written read as `default`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct InfiniteGrid<T> {
+    pub cells: HashMap<(isize, isize), T>,
+    pub default: T,
+}
+
+impl<T: Default + Clone> Default for InfiniteGrid<T> {
+    fn default() -> Self {
+        InfiniteGrid::new(T::default())
+    }
+}
+
+impl<T: Clone> InfiniteGrid<T> {
+    pub fn new(default: T) -> InfiniteGrid<T> {
+        InfiniteGrid {
+            cells: HashMap::new(),
+            default,
+        }
+    }
+
+    pub fn from_grid(grid: &Grid<T>, default: T) -> InfiniteGrid<T> {
+        let cells = grid
+            .state
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let pos = ((idx % grid.width) as isize, (idx / grid.width) as isize);
+                (pos, value.clone())
+            })
+            .collect();
+        InfiniteGrid { cells, default }
+    }
+
+    pub fn at(&self, pos: (isize, isize)) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.default)
+    }
+
+    pub fn get(&self, pos: (isize, isize)) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn set(&mut self, pos: (isize, isize), value: T) -> Option<T> {
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: (isize, isize)) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+
+    pub fn mut_at(&mut self, pos: (isize, isize)) -> &mut T {
+        let default = &self.default;
+        self.cells.entry(pos).or_insert_with(|| default.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // Positions that have been explicitly set, in no particular order
+    pub fn positions(&self) -> impl Iterator<Item = (isize, isize)> + '_ {
+        self.cells.keys().copied()
+    }
+
+    // Inclusive (min, max) corners of all set cells
+    pub fn bounds(&self) -> Option<((isize, isize), (isize, isize))> {
+        let (min_x, max_x) = self.cells.keys().map(|p| p.0).minmax().into_option()?;
+        let (min_y, max_y) = self.cells.keys().map(|p| p.1).minmax().into_option()?;
+        Some(((min_x, min_y), (max_x, max_y)))
+    }
+
+    pub fn get_neighbor_position(&self, pos: (isize, isize), cardinal: Cardinal) -> (isize, isize) {
+        let (dx, dy) = cardinal.delta();
+        (pos.0 + dx, pos.1 + dy)
+    }
+
+    pub fn get_neighbor_at(&self, pos: (isize, isize), cardinal: Cardinal) -> &T {
+        self.at(self.get_neighbor_position(pos, cardinal))
+    }
+
+    pub fn cardinal_neighbor_positions(&self, pos: (isize, isize)) -> Vec<(isize, isize)> {
+        let (x, y) = pos;
+        vec![(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+    }
+
+    pub fn neighbor_positions(&self, pos: (isize, isize)) -> Vec<(isize, isize)> {
+        let (x, y) = pos;
+        (-1..=1)
+            .cartesian_product(-1..=1)
+            .filter(|delta| *delta != (0, 0))
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .collect()
+    }
+
+    pub fn neighbors(&self, pos: (isize, isize)) -> impl Iterator<Item = &T> {
+        self.neighbor_positions(pos)
+            .into_iter()
+            .map(move |p| self.at(p))
+    }
+
+    // Copies the bounding box into a dense Grid, returning the offset of its (0, 0) cell
+    pub fn to_grid(&self) -> (Grid<T>, (isize, isize)) {
+        let ((min_x, min_y), (max_x, max_y)) = self.bounds().unwrap_or(((0, 0), (-1, -1)));
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let state = (min_y..=max_y)
+            .cartesian_product(min_x..=max_x)
+            .map(|(y, x)| self.at((x, y)).clone())
+            .collect_vec();
+        (
+            Grid {
+                state,
+                width,
+                height,
+            },
+            (min_x, min_y),
+        )
+    }
+}
+
+impl<T: Clone> Neighborhood for InfiniteGrid<T> {
+    type Pos = (isize, isize);
+
+    // Unbounded, so searches rely on their `passable` test to stop somewhere
+    fn adjacent(&self, pos: (isize, isize)) -> Vec<(isize, isize)> {
+        self.cardinal_neighbor_positions(pos)
+    }
+}
+
+impl<T: Display> Display for InfiniteGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        let Some((min_x, max_x)) = self.cells.keys().map(|p| p.0).minmax().into_option() else {
+            return writeln!(f);
+        };
+        let (min_y, max_y) = self
+            .cells
+            .keys()
+            .map(|p| p.1)
+            .minmax()
+            .into_option()
+            .unwrap();
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                write!(f, "{}", self.cells.get(&(x, y)).unwrap_or(&self.default))?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grid, InfiniteGrid};
+
+    #[test]
+    fn bounds_follow_sparse_inserts() {
+        let mut grid = InfiniteGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        grid.set((3, -2), '#');
+        grid.set((-4, 5), '#');
+        grid.set((0, 0), '#');
+        assert_eq!(grid.bounds(), Some(((-4, -2), (3, 5))));
+        assert_eq!(*grid.at((100, 100)), '.');
+
+        let (dense, offset) = grid.to_grid();
+        assert_eq!(offset, (-4, -2));
+        assert_eq!((dense.width, dense.height), (8, 8));
+        assert_eq!(*dense.at((7, 0)), '#');
+        assert_eq!(*dense.at((0, 7)), '#');
+        assert_eq!(*dense.at((4, 2)), '#');
+        assert_eq!(dense.state.iter().filter(|c| **c == '#').count(), 3);
+
+        grid.remove((-4, 5));
+        assert_eq!(grid.bounds(), Some(((0, -2), (3, 0))));
+    }
+
+    #[test]
+    fn round_trips_through_a_grid() {
+        let grid = Grid::from_lines(&["ab", "cd"], &|c| c);
+        let (dense, offset) = InfiniteGrid::from_grid(&grid, '.').to_grid();
+        assert_eq!(offset, (0, 0));
+        assert_eq!(dense, grid);
+    }
+}
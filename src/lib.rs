@@ -3,6 +3,7 @@ use itertools::Itertools;
 use log::{debug, error, info};
 use simple_logger::SimpleLogger;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
@@ -11,6 +12,58 @@ use std::thread;
 use std::time::Duration;
 use std::time::Instant;
 
+mod bit_grid;
+mod graph_cliques;
+mod graph_components;
+mod graph_dense;
+mod graph_export;
+mod graph_flow;
+mod graph_longest;
+mod graph_order;
+mod graph_paths;
+mod grid3;
+mod grid_diff;
+mod grid_graph;
+mod grid_parse;
+mod grid_render;
+mod grid_search;
+mod grid_transform;
+mod hex_grid;
+mod infinite_grid;
+mod octant;
+mod parallel;
+mod points;
+mod replay;
+mod search;
+mod serialize;
+mod simulation;
+mod wrapping_grid;
+
+pub use bit_grid::BitGrid;
+pub use graph_components::{Condensation, Cut};
+pub use graph_dense::{DenseGraph, Interner};
+pub use graph_export::GraphExporter;
+pub use graph_flow::Flow;
+pub use graph_paths::WeightedPath;
+pub use grid3::{Grid3, Pos3};
+pub use grid_diff::{CellChange, GridDiff};
+pub use grid_graph::{Corridor, JunctionGraphBuilder};
+pub use grid_parse::{split_sections, ParsedGrid};
+pub use grid_render::{Color, GridRenderer};
+pub use grid_search::{Orientation, PatternMatch, SequenceMatch};
+pub use hex_grid::{Hex, HexDirection, HexGrid};
+pub use infinite_grid::InfiniteGrid;
+pub use octant::Octant;
+pub use parallel::{par_map_range, GridOverlay};
+pub use points::{group_pairs, group_points, line_through, BoundingBox, Point, PointCloud};
+pub use replay::Replay;
+pub use search::{
+    bfs_distances, bfs_distances_by, flood_fill, flood_fill_by, regions, shortest_path_len,
+    Neighborhood,
+};
+pub use simulation::{Cycle, Simulation, SimulationEnd};
+pub use wrapping_grid::WrappingGrid;
+
 #[derive(Clone, PartialEq, Eq, Copy)]
 pub enum Part {
     One,
@@ -234,7 +287,8 @@ fn get_lines(file: &str) -> Vec<&str> {
     file.lines().collect_vec()
 }
 
-#[derive(Eq, PartialEq, Debug, Clone)]
+#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Grid<T> {
     pub state: Vec<T>,
     pub width: usize,
@@ -271,23 +325,19 @@ impl Cardinal {
         self.clockwise().clockwise().clockwise()
     }
 
+    pub const ALL: [Cardinal; 4] = [
+        Cardinal::North,
+        Cardinal::South,
+        Cardinal::East,
+        Cardinal::West,
+    ];
+
     pub fn all() -> Vec<Cardinal> {
-        vec![
-            Cardinal::North,
-            Cardinal::South,
-            Cardinal::East,
-            Cardinal::West,
-        ]
+        Cardinal::ALL.to_vec()
     }
 
     pub fn from_char(c: char) -> Cardinal {
-        match c {
-            'N' | '^' => Cardinal::North,
-            'S' | 'v' => Cardinal::South,
-            'E' | '>' => Cardinal::East,
-            'W' | '<' => Cardinal::West,
-            _ => panic!("Unknown cardinal"),
-        }
+        Cardinal::try_from(c).unwrap()
     }
 
     pub fn to_char(&self) -> char {
@@ -298,6 +348,106 @@ impl Cardinal {
             Cardinal::West => '<',
         }
     }
+
+    // Step in grid coordinates, where North is towards y = 0
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Cardinal::North => (0, -1),
+            Cardinal::South => (0, 1),
+            Cardinal::East => (1, 0),
+            Cardinal::West => (-1, 0),
+        }
+    }
+
+    pub fn from_delta(delta: (isize, isize)) -> Option<Cardinal> {
+        Cardinal::ALL.iter().copied().find(|c| c.delta() == delta)
+    }
+
+    pub fn turn(&self, turn: Turn) -> Cardinal {
+        match turn {
+            Turn::Left => self.counter_clockwise(),
+            Turn::Right => self.clockwise(),
+            Turn::Around => self.opposite(),
+            Turn::Straight => *self,
+        }
+    }
+
+    // Parses a run of directions such as "<^^>v", skipping whitespace and line breaks
+    pub fn parse_moves(s: &str) -> Result<Vec<Cardinal>, String> {
+        s.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(Cardinal::try_from)
+            .collect()
+    }
+}
+
+impl TryFrom<char> for Cardinal {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Cardinal, Self::Error> {
+        match c {
+            'N' | 'U' | '^' | '↑' => Ok(Cardinal::North),
+            'S' | 'D' | 'v' | '↓' => Ok(Cardinal::South),
+            'E' | 'R' | '>' | '→' => Ok(Cardinal::East),
+            'W' | 'L' | '<' | '←' => Ok(Cardinal::West),
+            _ => Err(format!("Unknown cardinal {c}")),
+        }
+    }
+}
+
+impl FromStr for Cardinal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "north" | "up" => Ok(Cardinal::North),
+            "south" | "down" => Ok(Cardinal::South),
+            "east" | "right" => Ok(Cardinal::East),
+            "west" | "left" => Ok(Cardinal::West),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Cardinal::try_from(c),
+                    _ => Err(format!("Unknown cardinal {s}")),
+                }
+            }
+        }
+    }
+}
+
+// Relative movement instruction, as in "turn L, walk 3"
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Turn {
+    Left,
+    Right,
+    Around,
+    Straight,
+}
+
+impl TryFrom<char> for Turn {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Turn, Self::Error> {
+        match c {
+            'L' => Ok(Turn::Left),
+            'R' => Ok(Turn::Right),
+            'U' => Ok(Turn::Around),
+            'F' | 'S' => Ok(Turn::Straight),
+            _ => Err(format!("Unknown turn {c}")),
+        }
+    }
+}
+
+impl FromStr for Turn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Turn::try_from(c),
+            _ => Err(format!("Unknown turn {s}")),
+        }
+    }
 }
 
 impl<T: Default + Clone> Grid<T> {
@@ -572,41 +722,16 @@ impl<T: Default + Clone> Grid<T> {
         (top_half, bottom_half)
     }
 
+    // One ray per direction, each running from next to `pos` to the edge. Rays come in
+    // (dx, dy) order: (-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)
     pub fn neighbors_along_directions(
         &self,
         pos: (usize, usize),
     ) -> Vec<impl Iterator<Item = (usize, usize)>> {
-        let (x, y) = pos;
-        let (width, height) = (self.width, self.height);
-        let delta = -1..=1;
-        delta
-            .clone()
-            .cartesian_product(delta)
-            .filter_map(move |(dx, dy)| {
-                if dx == 0 && dy == 0 {
-                    None
-                } else {
-                    let nums = 1..std::cmp::max(width, height);
-
-                    // Have to make an in scope copy to appease borrow checker
-                    let width = width;
-                    let height = height;
-
-                    Some(
-                        nums.filter_map(move |d| {
-                            let new_x = x as i64 + dx * d as i64;
-                            let new_y = y as i64 + dy * d as i64;
-
-                            if new_x >= 0 && new_y >= 0 {
-                                Some((new_x as usize, new_y as usize))
-                            } else {
-                                None
-                            }
-                        })
-                        .take_while(move |(new_x, new_y)| *new_x < width && *new_y < height),
-                    )
-                }
-            })
+        (-1..=1)
+            .cartesian_product(-1..=1)
+            .filter_map(Octant::from_delta)
+            .map(|octant| self.get_neighbors_along_octant(pos, octant).into_iter())
             .collect_vec()
     }
 
@@ -663,9 +788,12 @@ impl<T: Display> Display for Grid<T> {
     }
 }
 
+// Every vertex has an entry in `edges`, even sinks without outgoing edges. An undirected
+// graph stores each edge in both directions
 #[derive(Default, Debug)]
 pub struct Graph<V, E> {
     pub edges: HashMap<V, HashMap<V, E>>,
+    pub undirected: bool,
 }
 
 impl<V, E> Graph<V, E>
@@ -676,18 +804,140 @@ where
     pub fn new() -> Graph<V, E> {
         Graph {
             edges: HashMap::new(),
+            undirected: false,
+        }
+    }
+
+    pub fn new_undirected() -> Graph<V, E> {
+        Graph {
+            edges: HashMap::new(),
+            undirected: true,
         }
     }
 
+    pub fn is_directed(&self) -> bool {
+        !self.undirected
+    }
+
+    pub fn add_vertex(&mut self, vertex: V) {
+        self.edges.entry(vertex).or_default();
+    }
+
+    // In an undirected graph this also adds the edge back from `to`
     pub fn add_edge(&mut self, from: V, to: V, edge: E) {
+        if self.undirected {
+            self.edges
+                .entry(to.clone())
+                .or_default()
+                .insert(from.clone(), edge.clone());
+        } else {
+            self.add_vertex(to.clone());
+        }
         let from_entry = self.edges.entry(from);
         from_entry.or_default().insert(to, edge);
     }
 
+    // Both directions with their own payloads, like a keypad where going back is the opposite move
+    pub fn add_edge_pair(&mut self, a: V, b: V, forward: E, backward: E) {
+        self.edges
+            .entry(a.clone())
+            .or_default()
+            .insert(b.clone(), forward);
+        self.edges.entry(b).or_default().insert(a, backward);
+    }
+
+    pub fn remove_edge(&mut self, from: &V, to: &V) -> Option<E> {
+        if self.undirected {
+            if let Some(edges) = self.edges.get_mut(to) {
+                edges.remove(from);
+            }
+        }
+        self.edges.get_mut(from).and_then(|edges| edges.remove(to))
+    }
+
+    // Removes the vertex along with every edge into or out of it
+    pub fn remove_vertex(&mut self, vertex: &V) -> Option<HashMap<V, E>> {
+        let removed = self.edges.remove(vertex);
+        for edges in self.edges.values_mut() {
+            edges.remove(vertex);
+        }
+        removed
+    }
+
     pub fn get(&self, vertex: &V) -> Option<&HashMap<V, E>> {
         self.edges.get(vertex)
     }
 
+    pub fn contains_vertex(&self, vertex: &V) -> bool {
+        self.edges.contains_key(vertex)
+    }
+
+    pub fn contains_edge(&self, from: &V, to: &V) -> bool {
+        self.edges
+            .get(from)
+            .is_some_and(|edges| edges.contains_key(to))
+    }
+
+    pub fn edge(&self, from: &V, to: &V) -> Option<&E> {
+        self.edges.get(from).and_then(|edges| edges.get(to))
+    }
+
+    // Vertices reachable over one outgoing edge
+    pub fn neighbors<'a>(&'a self, vertex: &V) -> impl Iterator<Item = &'a V> {
+        self.edges
+            .get(vertex)
+            .into_iter()
+            .flat_map(|edges| edges.keys())
+    }
+
+    // Vertices with an edge into `vertex`
+    pub fn predecessors<'a>(&'a self, vertex: &'a V) -> impl Iterator<Item = &'a V> {
+        self.edges
+            .iter()
+            .filter(move |(_, edges)| edges.contains_key(vertex))
+            .map(|(from, _)| from)
+    }
+
+    pub fn out_degree(&self, vertex: &V) -> usize {
+        self.edges.get(vertex).map_or(0, |edges| edges.len())
+    }
+
+    pub fn in_degree(&self, vertex: &V) -> usize {
+        if self.undirected {
+            self.out_degree(vertex)
+        } else {
+            self.predecessors(vertex).count()
+        }
+    }
+
+    // Number of incident edges: in plus out for a directed graph
+    pub fn degree(&self, vertex: &V) -> usize {
+        if self.undirected {
+            self.out_degree(vertex)
+        } else {
+            self.in_degree(vertex) + self.out_degree(vertex)
+        }
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    // Undirected edges are only counted once
+    pub fn edge_count(&self) -> usize {
+        let stored = self.edges.values().map(|edges| edges.len()).sum::<usize>();
+        if self.undirected {
+            let self_loops = self
+                .edges
+                .iter()
+                .filter(|(vertex, edges)| edges.contains_key(vertex))
+                .count();
+            (stored + self_loops) / 2
+        } else {
+            stored
+        }
+    }
+
     pub fn debug(&self) {
         for (vertex, edges) in &self.edges {
             debug!("{:?}: {:?}", vertex, edges);
@@ -696,11 +946,7 @@ where
 
     pub fn debug_connections(&self) {
         for (vertex, edges) in &self.edges {
-            debug!(
-                "{:?}: {:?}",
-                vertex,
-                edges.iter().map(|(_, v)| v).collect_vec()
-            );
+            debug!("{:?}: {:?}", vertex, edges.values().collect_vec());
         }
     }
 
@@ -757,7 +1003,16 @@ where
         None
     }
 
+    // Weakly connected components: edge direction is ignored
     pub fn connected_components(&self) -> Vec<HashSet<V>> {
+        let mut adjacent: HashMap<&V, HashSet<&V>> = HashMap::new();
+        for (from, edges) in &self.edges {
+            adjacent.entry(from).or_default();
+            for to in edges.keys() {
+                adjacent.entry(from).or_default().insert(to);
+                adjacent.entry(to).or_default().insert(from);
+            }
+        }
         let mut visited = HashSet::new();
         let mut components = Vec::new();
         for vertex in self.all_vertices() {
@@ -770,9 +1025,9 @@ where
             visited.insert(vertex.clone());
             while let Some(v) = queue.pop_front() {
                 component.insert(v.clone());
-                for neighbor in self.edges.get(&v).unwrap().keys() {
-                    if !visited.contains(neighbor) && !component.contains(neighbor) {
-                        queue.push_back(neighbor.clone());
+                for neighbor in &adjacent[&v] {
+                    if !visited.contains(*neighbor) && !component.contains(*neighbor) {
+                        queue.push_back((*neighbor).clone());
                     }
                 }
             }
@@ -0,0 +1,247 @@
+use crate::{Cardinal, Grid};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+// Eight-way direction, clockwise from North. As with Cardinal, North is towards y = 0
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Octant {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Octant {
+    pub const ALL: [Octant; 8] = [
+        Octant::North,
+        Octant::NorthEast,
+        Octant::East,
+        Octant::SouthEast,
+        Octant::South,
+        Octant::SouthWest,
+        Octant::West,
+        Octant::NorthWest,
+    ];
+
+    pub const ORTHOGONAL: [Octant; 4] = [Octant::North, Octant::East, Octant::South, Octant::West];
+
+    pub const DIAGONAL: [Octant; 4] = [
+        Octant::NorthEast,
+        Octant::SouthEast,
+        Octant::SouthWest,
+        Octant::NorthWest,
+    ];
+
+    fn index(&self) -> usize {
+        Octant::ALL.iter().position(|o| o == self).unwrap()
+    }
+
+    fn rotate(&self, eighths: usize) -> Octant {
+        Octant::ALL[(self.index() + eighths) % 8]
+    }
+
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Octant::North => (0, -1),
+            Octant::NorthEast => (1, -1),
+            Octant::East => (1, 0),
+            Octant::SouthEast => (1, 1),
+            Octant::South => (0, 1),
+            Octant::SouthWest => (-1, 1),
+            Octant::West => (-1, 0),
+            Octant::NorthWest => (-1, -1),
+        }
+    }
+
+    pub fn from_delta(delta: (isize, isize)) -> Option<Octant> {
+        let normalized = (delta.0.signum(), delta.1.signum());
+        if delta.0 != 0 && delta.1 != 0 && delta.0.abs() != delta.1.abs() {
+            return None;
+        }
+        Octant::ALL
+            .iter()
+            .copied()
+            .find(|o| o.delta() == normalized)
+    }
+
+    pub fn opposite(&self) -> Octant {
+        self.rotate(4)
+    }
+
+    pub fn clockwise(&self) -> Octant {
+        self.rotate(2)
+    }
+
+    pub fn counter_clockwise(&self) -> Octant {
+        self.rotate(6)
+    }
+
+    pub fn clockwise_45(&self) -> Octant {
+        self.rotate(1)
+    }
+
+    pub fn counter_clockwise_45(&self) -> Octant {
+        self.rotate(7)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    pub fn to_char(&self) -> char {
+        match self {
+            Octant::North => '↑',
+            Octant::NorthEast => '↗',
+            Octant::East => '→',
+            Octant::SouthEast => '↘',
+            Octant::South => '↓',
+            Octant::SouthWest => '↙',
+            Octant::West => '←',
+            Octant::NorthWest => '↖',
+        }
+    }
+}
+
+impl From<Cardinal> for Octant {
+    fn from(cardinal: Cardinal) -> Octant {
+        match cardinal {
+            Cardinal::North => Octant::North,
+            Cardinal::East => Octant::East,
+            Cardinal::South => Octant::South,
+            Cardinal::West => Octant::West,
+        }
+    }
+}
+
+impl TryFrom<Octant> for Cardinal {
+    type Error = String;
+
+    fn try_from(octant: Octant) -> Result<Cardinal, Self::Error> {
+        match octant {
+            Octant::North => Ok(Cardinal::North),
+            Octant::East => Ok(Cardinal::East),
+            Octant::South => Ok(Cardinal::South),
+            Octant::West => Ok(Cardinal::West),
+            _ => Err(format!("{:?} is not a cardinal direction", octant)),
+        }
+    }
+}
+
+impl TryFrom<char> for Octant {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Octant, Self::Error> {
+        match c {
+            'N' | 'U' | '^' | '↑' => Ok(Octant::North),
+            '↗' => Ok(Octant::NorthEast),
+            'E' | 'R' | '>' | '→' => Ok(Octant::East),
+            '↘' => Ok(Octant::SouthEast),
+            'S' | 'D' | 'v' | '↓' => Ok(Octant::South),
+            '↙' => Ok(Octant::SouthWest),
+            'W' | 'L' | '<' | '←' => Ok(Octant::West),
+            '↖' => Ok(Octant::NorthWest),
+            _ => Err(format!("Unknown octant {c}")),
+        }
+    }
+}
+
+impl FromStr for Octant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "NE" | "UR" => Ok(Octant::NorthEast),
+            "SE" | "DR" => Ok(Octant::SouthEast),
+            "SW" | "DL" => Ok(Octant::SouthWest),
+            "NW" | "UL" => Ok(Octant::NorthWest),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Octant::try_from(c),
+                    _ => Err(format!("Unknown octant {s}")),
+                }
+            }
+        }
+    }
+}
+
+impl<T: Default + Clone> Grid<T> {
+    pub fn get_octant_neighbor_position(
+        &self,
+        pos: (usize, usize),
+        octant: Octant,
+    ) -> Option<(usize, usize)> {
+        let (dx, dy) = octant.delta();
+        let neighbor = (pos.0 as isize + dx, pos.1 as isize + dy);
+        self.get_isize(neighbor)
+            .map(|_| (neighbor.0 as usize, neighbor.1 as usize))
+    }
+
+    pub fn get_octant_neighbor_at(&self, pos: (usize, usize), octant: Octant) -> Option<&T> {
+        self.get_octant_neighbor_position(pos, octant)
+            .map(|neighbor| self.at(neighbor))
+    }
+
+    pub fn get_neighbors_along_octant(
+        &self,
+        pos: (usize, usize),
+        octant: Octant,
+    ) -> Vec<(usize, usize)> {
+        let mut neighbors = Vec::new();
+        let mut neighbor = self.get_octant_neighbor_position(pos, octant);
+        while let Some(n) = neighbor {
+            neighbors.push(n);
+            neighbor = self.get_octant_neighbor_position(n, octant);
+        }
+        neighbors
+    }
+
+    pub fn octant_neighbor_positions(&self, pos: (usize, usize)) -> Vec<(Octant, (usize, usize))> {
+        Octant::ALL
+            .iter()
+            .filter_map(|octant| {
+                self.get_octant_neighbor_position(pos, *octant)
+                    .map(|neighbor| (*octant, neighbor))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Grid;
+
+    #[test]
+    fn rays_keep_their_delta_order() {
+        let grid = Grid::new(vec![0; 9], 3, 3);
+        let firsts = grid
+            .neighbors_along_directions((1, 1))
+            .into_iter()
+            .map(|mut ray| ray.next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            firsts,
+            vec![
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 0),
+                (1, 2),
+                (2, 0),
+                (2, 1),
+                (2, 2)
+            ]
+        );
+
+        let lengths = grid
+            .neighbors_along_directions((0, 0))
+            .into_iter()
+            .map(|ray| ray.count())
+            .collect::<Vec<_>>();
+        assert_eq!(lengths, vec![0, 0, 0, 0, 2, 0, 2, 2]);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::Grid;
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+// Evaluates `f` for 0..len on rayon's global thread pool and returns the results in order
+pub fn par_map_range<U: Send>(len: usize, f: impl Fn(usize) -> U + Sync + Send) -> Vec<U> {
+    (0..len).into_par_iter().map(f).collect()
+}
+
+impl<T: Default + Clone + Sync> Grid<T> {
+    // Maps every position in parallel, in the same order as positions()
+    pub fn par_positions<U: Send>(&self, f: impl Fn((usize, usize)) -> U + Sync) -> Vec<U> {
+        par_map_range(self.width * self.height, |idx| f(self.index_to_pos(idx)))
+    }
+
+    pub fn par_filter_positions(
+        &self,
+        predicate: impl Fn((usize, usize)) -> bool + Sync,
+    ) -> Vec<(usize, usize)> {
+        self.par_positions(|pos| if predicate(pos) { Some(pos) } else { None })
+            .into_iter()
+            .flatten()
+            .collect_vec()
+    }
+
+    pub fn par_rows<U: Send>(&self, f: impl Fn(usize, &[T]) -> U + Sync) -> Vec<U> {
+        par_map_range(self.height, |y| {
+            f(y, &self.state[y * self.width..(y + 1) * self.width])
+        })
+    }
+
+    pub fn par_map<U: Default + Clone + Send>(
+        &self,
+        f: impl Fn((usize, usize), &T) -> U + Sync,
+    ) -> Grid<U> {
+        let state = self.par_positions(|pos| f(pos, self.at(pos)));
+        Grid::new(state, self.width, self.height)
+    }
+}
+
+// Borrowed grid with a handful of cells replaced, so "what if this cell were different"
+// searches don't need to clone the whole grid per candidate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridOverlay<'a, T> {
+    pub base: &'a Grid<T>,
+    pub overrides: HashMap<(usize, usize), T>,
+}
+
+impl<'a, T: Default + Clone> GridOverlay<'a, T> {
+    pub fn new(base: &'a Grid<T>) -> GridOverlay<'a, T> {
+        GridOverlay {
+            base,
+            overrides: HashMap::new(),
+        }
+    }
+
+    pub fn with(mut self, pos: (usize, usize), value: T) -> Self {
+        self.set(pos, value);
+        self
+    }
+
+    pub fn set(&mut self, pos: (usize, usize), value: T) {
+        assert!(
+            pos.0 < self.base.width && pos.1 < self.base.height,
+            "{:?} out of bounds",
+            pos
+        );
+        self.overrides.insert(pos, value);
+    }
+
+    pub fn at(&self, pos: (usize, usize)) -> &T {
+        self.overrides
+            .get(&pos)
+            .unwrap_or_else(|| self.base.at(pos))
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        let base = self.base.get(pos)?;
+        Some(self.overrides.get(&pos).unwrap_or(base))
+    }
+
+    pub fn get_isize(&self, pos: (isize, isize)) -> Option<&T> {
+        let base = self.base.get_isize(pos)?;
+        Some(
+            self.overrides
+                .get(&(pos.0 as usize, pos.1 as usize))
+                .unwrap_or(base),
+        )
+    }
+
+    // Copies the base grid with the overrides applied
+    pub fn to_grid(&self) -> Grid<T> {
+        let mut grid = self.base.clone();
+        for (pos, value) in &self.overrides {
+            *grid.mut_at(*pos) = value.clone();
+        }
+        grid
+    }
+}
+
+impl<T: Default + Clone> Grid<T> {
+    pub fn overlay(&self) -> GridOverlay<'_, T> {
+        GridOverlay::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{par_map_range, Grid};
+
+    #[test]
+    fn par_map_range_keeps_order() {
+        for len in [0, 1, 3, 1000] {
+            let squares = par_map_range(len, |i| i * i);
+            assert_eq!(squares, (0..len).map(|i| i * i).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn par_positions_match_positions() {
+        let grid = Grid::from_lines(&["abc", "def"], &|c| c);
+        let positions = grid.par_positions(|pos| pos);
+        assert_eq!(positions, grid.positions().collect::<Vec<_>>());
+        assert_eq!(
+            grid.par_map(|_, c| c.to_ascii_uppercase()).state,
+            vec!['A', 'B', 'C', 'D', 'E', 'F']
+        );
+        assert_eq!(grid.par_rows(|y, row| (y, row.len())), vec![(0, 3), (1, 3)]);
+    }
+
+    #[test]
+    fn overlay_stays_within_the_base_grid() {
+        let grid = Grid::from_lines(&["..", ".."], &|c| c);
+        let mut overlay = grid.overlay().with((1, 0), '#');
+        assert_eq!(overlay.get((1, 0)), Some(&'#'));
+        assert_eq!(overlay.get_isize((1, 0)), Some(&'#'));
+        overlay.overrides.insert((5, 5), '#');
+        assert_eq!(overlay.get((5, 5)), None);
+        assert_eq!(overlay.get_isize((5, 5)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn overlay_rejects_out_of_bounds_overrides() {
+        let grid = Grid::from_lines(&["..", ".."], &|c| c);
+        grid.overlay().set((2, 0), '#');
+    }
+}
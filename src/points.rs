@@ -0,0 +1,256 @@
+use crate::BitGrid;
+use itertools::Itertools;
+use num::integer::gcd;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+pub type Point = (isize, isize);
+
+// Inclusive on both corners
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    pub fn new(min: Point, max: Point) -> BoundingBox {
+        BoundingBox { min, max }
+    }
+
+    // Covers (0, 0) up to but not including (width, height), like a Grid of that size
+    pub fn from_size(width: usize, height: usize) -> BoundingBox {
+        BoundingBox {
+            min: (0, 0),
+            max: (width as isize - 1, height as isize - 1),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.0 - self.min.0 + 1).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.1 - self.min.1 + 1).max(0) as usize
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.0..=self.max.0).contains(&p.0) && (self.min.1..=self.max.1).contains(&p.1)
+    }
+
+    // Splits the box into columns x rows roughly equal cells, returning the cell of `p`
+    fn cell_of(&self, p: Point, columns: usize, rows: usize) -> Option<(usize, usize)> {
+        if !self.contains(p) {
+            return None;
+        }
+        let x = (p.0 - self.min.0) as usize * columns / self.width();
+        let y = (p.1 - self.min.1) as usize * rows / self.height();
+        Some((x, y))
+    }
+}
+
+// Every lattice point on the line through `a` and `b` that lies inside `bounds`, walking
+// in steps of the reduced direction vector, ordered from `a` towards `b`
+pub fn line_through(a: Point, b: Point, bounds: &BoundingBox) -> Vec<Point> {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    if (dx, dy) == (0, 0) {
+        return if bounds.contains(a) { vec![a] } else { vec![] };
+    }
+    let divisor = gcd(dx, dy);
+    let step = (dx / divisor, dy / divisor);
+
+    // Range of k for which a + k * step lies inside the bounds, axis by axis, so that `a`
+    // itself may be outside them
+    let mut first = isize::MIN;
+    let mut last = isize::MAX;
+    for (origin, step, min, max) in [
+        (a.0, step.0, bounds.min.0, bounds.max.0),
+        (a.1, step.1, bounds.min.1, bounds.max.1),
+    ] {
+        if step == 0 {
+            if !(min..=max).contains(&origin) {
+                return vec![];
+            }
+            continue;
+        }
+        let (low, high) = if step > 0 { (min, max) } else { (max, min) };
+        first = first.max(div_ceil(low - origin, step));
+        last = last.min(div_floor(high - origin, step));
+    }
+    (first..=last)
+        .map(|k| (a.0 + k * step.0, a.1 + k * step.1))
+        .collect()
+}
+
+fn div_floor(a: isize, b: isize) -> isize {
+    let q = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+fn div_ceil(a: isize, b: isize) -> isize {
+    -div_floor(-a, b)
+}
+
+// Groups points by a key, e.g. antennae by frequency
+pub fn group_points<K: Hash + Eq>(
+    points: impl IntoIterator<Item = (K, Point)>,
+) -> HashMap<K, Vec<Point>> {
+    points
+        .into_iter()
+        .fold(HashMap::new(), |mut groups, (key, point)| {
+            groups.entry(key).or_default().push(point);
+            groups
+        })
+}
+
+// Every unordered pair of points that share a group
+pub fn group_pairs<K>(groups: &HashMap<K, Vec<Point>>) -> impl Iterator<Item = (&K, Point, Point)> {
+    groups.iter().flat_map(|(key, points)| {
+        points
+            .iter()
+            .tuple_combinations()
+            .map(move |(a, b)| (key, *a, *b))
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PointCloud {
+    pub points: Vec<Point>,
+}
+
+impl PointCloud {
+    pub fn new(points: Vec<Point>) -> PointCloud {
+        PointCloud { points }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let (min_x, max_x) = self.points.iter().map(|p| p.0).minmax().into_option()?;
+        let (min_y, max_y) = self.points.iter().map(|p| p.1).minmax().into_option()?;
+        Some(BoundingBox::new((min_x, min_y), (max_x, max_y)))
+    }
+
+    // Counts per cell when `bounds` is cut into columns x rows equal parts, indexed [y][x]
+    pub fn partition_counts(
+        &self,
+        bounds: &BoundingBox,
+        columns: usize,
+        rows: usize,
+    ) -> Vec<Vec<usize>> {
+        let mut counts = vec![vec![0; columns]; rows];
+        for p in &self.points {
+            if let Some((x, y)) = bounds.cell_of(*p, columns, rows) {
+                counts[y][x] += 1;
+            }
+        }
+        counts
+    }
+
+    // Top left, top right, bottom left, bottom right. Points on the middle row or column
+    // of an odd sized box belong to no quadrant
+    pub fn quadrant_counts(&self, bounds: &BoundingBox) -> [usize; 4] {
+        let mid_x = bounds.min.0 + bounds.width() as isize / 2;
+        let mid_y = bounds.min.1 + bounds.height() as isize / 2;
+        let odd_x = bounds.width() % 2 == 1;
+        let odd_y = bounds.height() % 2 == 1;
+        let mut counts = [0; 4];
+        for p in self.points.iter().filter(|p| bounds.contains(**p)) {
+            if (odd_x && p.0 == mid_x) || (odd_y && p.1 == mid_y) {
+                continue;
+            }
+            let right = p.0 >= mid_x;
+            let bottom = p.1 >= mid_y;
+            counts[(bottom as usize) * 2 + right as usize] += 1;
+        }
+        counts
+    }
+
+    pub fn mean(&self) -> Option<(f64, f64)> {
+        if self.points.is_empty() {
+            return None;
+        }
+        let n = self.points.len() as f64;
+        let (sum_x, sum_y) = self
+            .points
+            .iter()
+            .fold((0.0, 0.0), |(x, y), p| (x + p.0 as f64, y + p.1 as f64));
+        Some((sum_x / n, sum_y / n))
+    }
+
+    // Population variance along each axis
+    pub fn variance(&self) -> Option<(f64, f64)> {
+        let (mean_x, mean_y) = self.mean()?;
+        let n = self.points.len() as f64;
+        let (var_x, var_y) = self.points.iter().fold((0.0, 0.0), |(x, y), p| {
+            (
+                x + (p.0 as f64 - mean_x).powi(2),
+                y + (p.1 as f64 - mean_y).powi(2),
+            )
+        });
+        Some((var_x / n, var_y / n))
+    }
+
+    // Fraction of points with at least one other point among their eight neighbors.
+    // Random scatter scores low, drawn pictures score close to 1
+    pub fn clustering_score(&self) -> f64 {
+        if self.points.is_empty() {
+            return 0.0;
+        }
+        let occupied: HashSet<&Point> = self.points.iter().collect();
+        let clustered = occupied
+            .iter()
+            .filter(|(x, y)| {
+                (-1..=1)
+                    .cartesian_product(-1..=1)
+                    .filter(|delta| *delta != (0, 0))
+                    .any(|(dx, dy)| occupied.contains(&(x + dx, y + dy)))
+            })
+            .count();
+        clustered as f64 / occupied.len() as f64
+    }
+
+    pub fn to_bit_grid(&self, bounds: &BoundingBox) -> BitGrid {
+        let mut grid = BitGrid::new_empty(bounds.width(), bounds.height());
+        for p in self.points.iter().filter(|p| bounds.contains(**p)) {
+            grid.set(
+                ((p.0 - bounds.min.0) as usize, (p.1 - bounds.min.1) as usize),
+                true,
+            );
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{line_through, BoundingBox};
+
+    #[test]
+    fn line_through_from_outside_the_bounds() {
+        let bounds = BoundingBox::from_size(5, 5);
+        let diagonal = (0..5).map(|i| (i, i)).collect::<Vec<_>>();
+        assert_eq!(line_through((0, 0), (-1, -1), &bounds), {
+            let mut reversed = diagonal.clone();
+            reversed.reverse();
+            reversed
+        });
+        assert_eq!(line_through((-1, -1), (0, 0), &bounds), diagonal);
+        assert_eq!(line_through((-3, 7), (-2, 7), &bounds), vec![]);
+        assert_eq!(line_through((9, 1), (7, 2), &bounds), vec![(3, 4)]);
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{Grid, Simulation};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io;
+use std::io::{BufRead, Write};
+use std::sync::mpsc;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+const HELP: &str =
+    "[enter/n] next  [b] back  [p] play/pause  [+/-] speed  [g N] go to frame  [q] quit";
+
+// A recorded sequence of rendered frames that can be stepped through in the terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    pub frames: Vec<String>,
+    pub delay: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Playback {
+    frame: usize,
+    playing: bool,
+    delay: Duration,
+}
+
+impl Playback {
+    // Applies a single command, returning false once the viewer should exit
+    fn handle(&mut self, command: &str, frame_count: usize) -> bool {
+        let last = frame_count.saturating_sub(1);
+        let mut parts = command.split_whitespace();
+        match parts.next().unwrap_or("n") {
+            "n" => {
+                self.playing = false;
+                self.frame = (self.frame + 1).min(last);
+            }
+            "b" => {
+                self.playing = false;
+                self.frame = self.frame.saturating_sub(1);
+            }
+            "p" => self.playing = !self.playing,
+            "+" => self.delay = (self.delay / 2).max(Duration::from_millis(1)),
+            "-" => self.delay *= 2,
+            "g" => {
+                if let Some(frame) = parts.next().and_then(|n| n.parse::<usize>().ok()) {
+                    self.frame = frame.min(last);
+                }
+            }
+            "q" => return false,
+            _ => (),
+        }
+        true
+    }
+}
+
+impl Replay {
+    pub fn new() -> Replay {
+        Replay {
+            frames: Vec::new(),
+            delay: Duration::from_millis(100),
+        }
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn push_frame(&mut self, frame: String) {
+        self.frames.push(frame);
+    }
+
+    pub fn from_grids<T: Display>(grids: &[Grid<T>]) -> Replay {
+        let mut replay = Replay::new();
+        for grid in grids {
+            replay.push_frame(grid.to_string());
+        }
+        replay
+    }
+
+    // Records the current state and then up to `max_steps` more, stopping early if the
+    // simulation halts
+    pub fn from_simulation<T, S: Clone + Eq + Hash>(
+        simulation: &mut Simulation<'_, T, S>,
+        max_steps: usize,
+        render: impl Fn(&Grid<T>, &S) -> String,
+    ) -> Replay {
+        let mut replay = Replay::new();
+        replay.push_frame(render(simulation.grid, &simulation.state));
+        for _ in 0..max_steps {
+            if !simulation.step() {
+                break;
+            }
+            replay.push_frame(render(simulation.grid, &simulation.state));
+        }
+        replay
+    }
+
+    // Writes every frame with a header line, for diffing or reading back later
+    pub fn export(&self, out: &mut impl Write) -> io::Result<()> {
+        for (idx, frame) in self.frames.iter().enumerate() {
+            writeln!(out, "=== Frame {} / {} ===", idx, self.frames.len() - 1)?;
+            writeln!(out, "{}", frame.trim_matches('\n'))?;
+        }
+        Ok(())
+    }
+
+    // Interactive viewer on the terminal. Commands are read a line at a time from stdin
+    pub fn play(&self) -> io::Result<()> {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                match line {
+                    Ok(line) => {
+                        if sender.send(line).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+        self.play_with(&receiver, &mut io::stdout())
+    }
+
+    pub fn play_with(&self, commands: &Receiver<String>, out: &mut impl Write) -> io::Result<()> {
+        if self.frames.is_empty() {
+            return Ok(());
+        }
+        let mut playback = Playback {
+            frame: 0,
+            playing: false,
+            delay: self.delay,
+        };
+        loop {
+            self.draw(&playback, out)?;
+            let command = if playback.playing {
+                match commands.recv_timeout(playback.delay) {
+                    Ok(command) => Some(command),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                }
+            } else {
+                match commands.recv() {
+                    Ok(command) => Some(command),
+                    Err(_) => return Ok(()),
+                }
+            };
+            match command {
+                Some(command) => {
+                    if !playback.handle(&command, self.frames.len()) {
+                        return Ok(());
+                    }
+                }
+                None => {
+                    if playback.frame + 1 < self.frames.len() {
+                        playback.frame += 1;
+                    } else {
+                        playback.playing = false;
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw(&self, playback: &Playback, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "\x1b[2J\x1b[H")?;
+        writeln!(out, "{}", self.frames[playback.frame].trim_matches('\n'))?;
+        writeln!(
+            out,
+            "Frame {} / {} ({}, {:?} per frame)",
+            playback.frame,
+            self.frames.len() - 1,
+            if playback.playing {
+                "playing"
+            } else {
+                "paused"
+            },
+            playback.delay
+        )?;
+        writeln!(out, "{}", HELP)?;
+        out.flush()
+    }
+}
+
+impl Default for Replay {
+    fn default() -> Self {
+        Replay::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Playback, Replay};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn replay() -> Replay {
+        Replay {
+            frames: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            delay: Duration::from_millis(100),
+        }
+    }
+
+    #[test]
+    fn playback_steps_within_the_frames() {
+        let mut playback = Playback {
+            frame: 0,
+            playing: true,
+            delay: Duration::from_millis(100),
+        };
+        assert!(playback.handle("b", 3));
+        assert_eq!((playback.frame, playback.playing), (0, false));
+        for _ in 0..5 {
+            playback.handle("", 3);
+        }
+        assert_eq!(playback.frame, 2);
+        playback.handle("g 1", 3);
+        assert_eq!(playback.frame, 1);
+        playback.handle("g 99", 3);
+        assert_eq!(playback.frame, 2);
+        playback.handle("g x", 3);
+        assert_eq!(playback.frame, 2);
+        playback.handle("+", 3);
+        assert_eq!(playback.delay, Duration::from_millis(50));
+        playback.handle("-", 3);
+        playback.handle("-", 3);
+        assert_eq!(playback.delay, Duration::from_millis(200));
+        playback.handle("p", 3);
+        assert!(playback.playing);
+        assert!(!playback.handle("q", 3));
+    }
+
+    #[test]
+    fn play_with_draws_each_visited_frame() {
+        let (sender, receiver) = mpsc::channel();
+        for command in ["n", "n", "b", "q"].iter() {
+            sender.send(command.to_string()).unwrap();
+        }
+        let mut out = Vec::new();
+        replay().play_with(&receiver, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let frames = out
+            .lines()
+            .filter(|line| line.starts_with("Frame "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            vec![
+                "Frame 0 / 2 (paused, 100ms per frame)",
+                "Frame 1 / 2 (paused, 100ms per frame)",
+                "Frame 2 / 2 (paused, 100ms per frame)",
+                "Frame 1 / 2 (paused, 100ms per frame)",
+            ]
+        );
+    }
+
+    #[test]
+    fn export_writes_a_header_per_frame() {
+        let mut out = Vec::new();
+        replay().export(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "=== Frame 0 / 2 ===\na\n=== Frame 1 / 2 ===\nb\n=== Frame 2 / 2 ===\nc\n"
+        );
+        let mut out = Vec::new();
+        Replay::new().export(&mut out).unwrap();
+        assert!(out.is_empty());
+    }
+}
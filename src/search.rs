@@ -0,0 +1,138 @@
+use crate::Grid;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// Anything whose cells have a notion of adjacency: square, cubic and hex grids all share
+// the searches below through this trait
+pub trait Neighborhood {
+    type Pos: Copy + Eq + Hash;
+
+    fn adjacent(&self, pos: Self::Pos) -> Vec<Self::Pos>;
+}
+
+impl<T: Default + Clone> Neighborhood for Grid<T> {
+    type Pos = (usize, usize);
+
+    fn adjacent(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        self.cardinal_neighbor_positions(pos)
+    }
+}
+
+// Breadth first distances from `start` to every reachable position
+pub fn bfs_distances_by<P: Copy + Eq + Hash>(
+    start: P,
+    neighbors: impl Fn(P) -> Vec<P>,
+) -> HashMap<P, usize> {
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert(start, 0);
+    queue.push_back(start);
+    while let Some(pos) = queue.pop_front() {
+        let distance = distances[&pos];
+        for neighbor in neighbors(pos) {
+            if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                entry.insert(distance + 1);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    distances
+}
+
+pub fn flood_fill_by<P: Copy + Eq + Hash>(start: P, neighbors: impl Fn(P) -> Vec<P>) -> HashSet<P> {
+    bfs_distances_by(start, neighbors).into_keys().collect()
+}
+
+pub fn bfs_distances<N: Neighborhood>(
+    space: &N,
+    start: N::Pos,
+    passable: impl Fn(N::Pos) -> bool,
+) -> HashMap<N::Pos, usize> {
+    bfs_distances_by(start, |pos| {
+        space
+            .adjacent(pos)
+            .into_iter()
+            .filter(|neighbor| passable(*neighbor))
+            .collect()
+    })
+}
+
+// Every position reachable from `start` through passable cells, including `start` itself
+pub fn flood_fill<N: Neighborhood>(
+    space: &N,
+    start: N::Pos,
+    passable: impl Fn(N::Pos) -> bool,
+) -> HashSet<N::Pos> {
+    bfs_distances(space, start, passable).into_keys().collect()
+}
+
+pub fn shortest_path_len<N: Neighborhood>(
+    space: &N,
+    start: N::Pos,
+    end: N::Pos,
+    passable: impl Fn(N::Pos) -> bool,
+) -> Option<usize> {
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    visited.insert(start);
+    queue.push_back((start, 0));
+    while let Some((pos, distance)) = queue.pop_front() {
+        if pos == end {
+            return Some(distance);
+        }
+        for neighbor in space.adjacent(pos) {
+            if passable(neighbor) && visited.insert(neighbor) {
+                queue.push_back((neighbor, distance + 1));
+            }
+        }
+    }
+    None
+}
+
+// Splits `positions` into connected regions, where neighbors are joined if `same_region` holds
+pub fn regions<N: Neighborhood>(
+    space: &N,
+    positions: impl IntoIterator<Item = N::Pos>,
+    same_region: impl Fn(N::Pos, N::Pos) -> bool,
+) -> Vec<HashSet<N::Pos>> {
+    let mut assigned = HashSet::new();
+    let mut regions = Vec::new();
+    for pos in positions {
+        if assigned.contains(&pos) {
+            continue;
+        }
+        let region = flood_fill_by(pos, |p| {
+            space
+                .adjacent(p)
+                .into_iter()
+                .filter(|neighbor| same_region(p, *neighbor))
+                .collect()
+        });
+        assigned.extend(region.iter().copied());
+        regions.push(region);
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bfs_distances, flood_fill, Grid, InfiniteGrid};
+
+    #[test]
+    fn wrapping_and_infinite_grids_are_searchable() {
+        let grid = Grid::from_lines(&["..#", "###", "..#"], &|c| c);
+        let wrapping = grid.wrapping();
+        let distances = bfs_distances(&wrapping, (0, 0), |pos| *grid.at(pos) == '.');
+        // Straight up through the top edge rather than around the walls
+        assert_eq!(distances[&(0, 2)], 1);
+
+        let infinite = InfiniteGrid::from_grid(&grid, '.');
+        let inside = |(x, y): (isize, isize)| (-1..=3).contains(&x) && (-1..=3).contains(&y);
+        let reached = flood_fill(&infinite, (0, 0), |pos| {
+            inside(pos) && *infinite.at(pos) == '.'
+        });
+        assert!(reached.contains(&(0, 2)));
+        assert!(!reached.contains(&(1, 1)));
+    }
+}
@@ -0,0 +1,180 @@
+use crate::Grid;
+use itertools::Itertools;
+
+impl<T: Default + Clone> Grid<T> {
+    // Inverse of from_lines: one line per row, using `to_char` for each cell
+    pub fn to_text(&self, to_char: &dyn Fn(&T) -> char) -> String {
+        (0..self.height)
+            .map(|y| self.row(y).map(to_char).collect::<String>())
+            .join("\n")
+    }
+
+    pub fn from_text(text: &str, transformer: &dyn Fn(char) -> T) -> Result<Grid<T>, String> {
+        Grid::try_from_lines(&text.lines().collect_vec(), transformer)
+    }
+}
+
+#[cfg(feature = "serialize")]
+mod formats {
+    use crate::{Graph, Grid};
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt::Debug;
+    use std::hash::Hash;
+
+    // Graphs are stored as a flat edge list, since JSON maps can only have string keys.
+    // Vertices are listed separately so ones without outgoing edges survive the round trip
+    #[derive(Serialize)]
+    struct GraphRecordRef<'a, V, E> {
+        undirected: bool,
+        vertices: Vec<&'a V>,
+        edges: Vec<(&'a V, &'a V, &'a E)>,
+    }
+
+    #[derive(Deserialize)]
+    struct GraphRecord<V, E> {
+        #[serde(default)]
+        undirected: bool,
+        vertices: Vec<V>,
+        edges: Vec<(V, V, E)>,
+    }
+
+    impl<V: Serialize, E: Serialize> Serialize for Graph<V, E> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            GraphRecordRef {
+                undirected: self.undirected,
+                vertices: self.edges.keys().collect(),
+                edges: self
+                    .edges
+                    .iter()
+                    .flat_map(|(from, edges)| edges.iter().map(move |(to, e)| (from, to, e)))
+                    .collect(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, V, E> Deserialize<'de> for Graph<V, E>
+    where
+        V: Deserialize<'de> + Eq + Hash + Clone + Debug,
+        E: Deserialize<'de> + Eq + Hash + Clone + Debug,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let record = GraphRecord::deserialize(deserializer)?;
+            let mut graph = if record.undirected {
+                Graph::new_undirected()
+            } else {
+                Graph::new()
+            };
+            for vertex in record.vertices {
+                graph.add_vertex(vertex);
+            }
+            for (from, to, edge) in record.edges {
+                graph.add_edge(from, to, edge);
+            }
+            Ok(graph)
+        }
+    }
+
+    fn check_size<T>(grid: Grid<T>) -> Result<Grid<T>, String> {
+        if grid.state.len() == grid.width * grid.height {
+            Ok(grid)
+        } else {
+            Err(format!(
+                "Grid state has {} cells but should have {}x{}",
+                grid.state.len(),
+                grid.width,
+                grid.height
+            ))
+        }
+    }
+
+    impl<T: Default + Clone + Serialize + DeserializeOwned> Grid<T> {
+        pub fn to_json(&self) -> Result<String, String> {
+            serde_json::to_string(self).map_err(|e| e.to_string())
+        }
+
+        pub fn from_json(json: &str) -> Result<Grid<T>, String> {
+            check_size(serde_json::from_str(json).map_err(|e| e.to_string())?)
+        }
+
+        pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+            bincode::serialize(self).map_err(|e| e.to_string())
+        }
+
+        pub fn from_bytes(bytes: &[u8]) -> Result<Grid<T>, String> {
+            check_size(bincode::deserialize(bytes).map_err(|e| e.to_string())?)
+        }
+    }
+
+    impl<V, E> Graph<V, E>
+    where
+        V: Eq + Hash + Clone + Debug + Serialize + DeserializeOwned,
+        E: Eq + Hash + Clone + Debug + Serialize + DeserializeOwned,
+    {
+        pub fn to_json(&self) -> Result<String, String> {
+            serde_json::to_string(self).map_err(|e| e.to_string())
+        }
+
+        pub fn from_json(json: &str) -> Result<Graph<V, E>, String> {
+            serde_json::from_str(json).map_err(|e| e.to_string())
+        }
+
+        pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+            bincode::serialize(self).map_err(|e| e.to_string())
+        }
+
+        pub fn from_bytes(bytes: &[u8]) -> Result<Graph<V, E>, String> {
+            bincode::deserialize(bytes).map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Grid;
+
+    #[test]
+    fn text_round_trip_with_wide_chars() {
+        let grid = Grid::new(vec!['█', '.', '.', '█'], 2, 2);
+        let text = grid.to_text(&|c| *c);
+        assert_eq!(text, "█.\n.█");
+        assert_eq!(Grid::from_text(&text, &|c| c).unwrap(), grid);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn grid_json_and_bytes_round_trip() {
+        let grid = Grid::new(vec![1u8, 2, 3, 4, 5, 6], 3, 2);
+        assert_eq!(Grid::from_json(&grid.to_json().unwrap()).unwrap(), grid);
+        assert_eq!(Grid::from_bytes(&grid.to_bytes().unwrap()).unwrap(), grid);
+        assert!(Grid::<u8>::from_json(r#"{"state":[1,2,3],"width":2,"height":2}"#).is_err());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn graph_json_and_bytes_round_trip() {
+        use crate::Graph;
+
+        for undirected in [false, true] {
+            let mut graph = if undirected {
+                Graph::new_undirected()
+            } else {
+                Graph::new()
+            };
+            graph.add_edge("a".to_string(), "b".to_string(), 3);
+            graph.add_edge("b".to_string(), "c".to_string(), 4);
+            graph.add_vertex("island".to_string());
+
+            let from_json: Graph<String, i32> =
+                Graph::from_json(&graph.to_json().unwrap()).unwrap();
+            let from_bytes: Graph<String, i32> =
+                Graph::from_bytes(&graph.to_bytes().unwrap()).unwrap();
+            for copy in [from_json, from_bytes] {
+                assert_eq!(copy.undirected, undirected);
+                assert_eq!(copy.edges, graph.edges);
+                assert!(copy.contains_vertex(&"island".to_string()));
+            }
+        }
+    }
+}
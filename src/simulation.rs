@@ -0,0 +1,233 @@
+use crate::Grid;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Cycle {
+    // Step at which the repeating section first starts
+    pub start: usize,
+    pub length: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum SimulationEnd {
+    // The step function gave up after this many steps
+    Halted(usize),
+    Cycle(Cycle),
+}
+
+type StepFn<'a, T, S> = Box<dyn Fn(&Grid<T>, &S) -> Option<S> + 'a>;
+
+// Steps a user defined state over a fixed grid. The step function returns None once
+// the simulation is over (e.g. the agent walked off the grid)
+pub struct Simulation<'a, T, S> {
+    pub grid: &'a Grid<T>,
+    pub state: S,
+    pub steps: usize,
+    step_fn: StepFn<'a, T, S>,
+    history: Option<Vec<S>>,
+    halted: bool,
+}
+
+impl<'a, T, S: Clone + Eq + Hash> Simulation<'a, T, S> {
+    pub fn new(
+        grid: &'a Grid<T>,
+        initial: S,
+        step_fn: impl Fn(&Grid<T>, &S) -> Option<S> + 'a,
+    ) -> Simulation<'a, T, S> {
+        Simulation {
+            grid,
+            state: initial,
+            steps: 0,
+            step_fn: Box::new(step_fn),
+            history: None,
+            halted: false,
+        }
+    }
+
+    // Keep every state that is actually simulated. Steps skipped by fast_forward are not recorded
+    pub fn with_history(mut self) -> Self {
+        self.history = Some(vec![self.state.clone()]);
+        self
+    }
+
+    pub fn history(&self) -> Option<&[S]> {
+        self.history.as_deref()
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn step(&mut self) -> bool {
+        if self.halted {
+            return false;
+        }
+        match (self.step_fn)(self.grid, &self.state) {
+            Some(next) => {
+                if let Some(history) = &mut self.history {
+                    history.push(next.clone());
+                }
+                self.state = next;
+                self.steps += 1;
+                true
+            }
+            None => {
+                self.halted = true;
+                false
+            }
+        }
+    }
+
+    // Returns how many steps were taken, which is less than `n` if the simulation halted
+    pub fn run(&mut self, n: usize) -> usize {
+        (0..n).take_while(|_| self.step()).count()
+    }
+
+    // Never returns if the simulation loops forever; use find_cycle for that
+    pub fn run_until_halt(&mut self) -> usize {
+        let start = self.steps;
+        while self.step() {}
+        self.steps - start
+    }
+
+    // Runs until a state repeats or the simulation halts, remembering every state seen
+    pub fn find_cycle(&mut self) -> SimulationEnd {
+        let mut seen = HashMap::new();
+        loop {
+            if let Some(start) = seen.get(&self.state) {
+                return SimulationEnd::Cycle(Cycle {
+                    start: *start,
+                    length: self.steps - start,
+                });
+            }
+            seen.insert(self.state.clone(), self.steps);
+            if !self.step() {
+                return SimulationEnd::Halted(self.steps);
+            }
+        }
+    }
+
+    // Same answer as find_cycle using Brent's algorithm, which only keeps two states in
+    // memory. Does not advance the simulation
+    pub fn find_cycle_brent(&self) -> SimulationEnd {
+        let step = |state: &S| (self.step_fn)(self.grid, state);
+        let mut power = 1;
+        let mut length = 1;
+        let mut tortoise = self.state.clone();
+        let mut hare = match step(&tortoise) {
+            Some(next) => next,
+            None => return SimulationEnd::Halted(self.steps),
+        };
+        let mut hare_steps = 1;
+        while tortoise != hare {
+            if power == length {
+                tortoise = hare.clone();
+                power *= 2;
+                length = 0;
+            }
+            hare = match step(&hare) {
+                Some(next) => next,
+                None => return SimulationEnd::Halted(self.steps + hare_steps),
+            };
+            hare_steps += 1;
+            length += 1;
+        }
+
+        let mut tortoise = self.state.clone();
+        let mut hare = self.state.clone();
+        for _ in 0..length {
+            hare = step(&hare).unwrap();
+        }
+        let mut start = 0;
+        while tortoise != hare {
+            tortoise = step(&tortoise).unwrap();
+            hare = step(&hare).unwrap();
+            start += 1;
+        }
+        SimulationEnd::Cycle(Cycle {
+            start: self.steps + start,
+            length,
+        })
+    }
+
+    // Advances `n` steps, skipping whole cycles once a repeated state is found
+    pub fn fast_forward(&mut self, n: usize) -> usize {
+        let start = self.steps;
+        let target = self.steps + n;
+        let mut seen = HashMap::new();
+        while self.steps < target {
+            if let Some(first) = seen.get(&self.state) {
+                let length = self.steps - first;
+                let remaining = (target - self.steps) % length;
+                self.steps = target - remaining;
+                self.run(remaining);
+                break;
+            }
+            seen.insert(self.state.clone(), self.steps);
+            if !self.step() {
+                break;
+            }
+        }
+        self.steps - start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cycle, Simulation, SimulationEnd};
+    use crate::Grid;
+
+    // 0, 1, ..., 7 and then back to 3: a tail of 3 steps into a cycle of 5
+    fn rho(_: &Grid<u8>, state: &usize) -> Option<usize> {
+        Some(if *state < 7 { state + 1 } else { 3 })
+    }
+
+    #[test]
+    fn cycle_detectors_agree() {
+        let grid = Grid::new(vec![0], 1, 1);
+        let expected = SimulationEnd::Cycle(Cycle {
+            start: 3,
+            length: 5,
+        });
+        assert_eq!(Simulation::new(&grid, 0, rho).find_cycle_brent(), expected);
+        assert_eq!(Simulation::new(&grid, 0, rho).find_cycle(), expected);
+
+        // Starting inside the cycle leaves no tail
+        let expected = SimulationEnd::Cycle(Cycle {
+            start: 0,
+            length: 5,
+        });
+        assert_eq!(Simulation::new(&grid, 5, rho).find_cycle_brent(), expected);
+        assert_eq!(Simulation::new(&grid, 5, rho).find_cycle(), expected);
+
+        let counter =
+            |_: &Grid<u8>, state: &usize| if *state < 10 { Some(state + 1) } else { None };
+        assert_eq!(
+            Simulation::new(&grid, 0, counter).find_cycle_brent(),
+            SimulationEnd::Halted(10)
+        );
+        assert_eq!(
+            Simulation::new(&grid, 0, counter).find_cycle(),
+            SimulationEnd::Halted(10)
+        );
+    }
+
+    #[test]
+    fn fast_forward_skips_whole_cycles() {
+        let grid = Grid::new(vec![0], 1, 1);
+        let n = 1_000_000_003;
+        let mut simulation = Simulation::new(&grid, 0, rho);
+        assert_eq!(simulation.fast_forward(n), n);
+        assert_eq!(simulation.steps, n);
+        assert_eq!(simulation.state, 3 + (n - 3) % 5);
+
+        // Continuing from part way through matches plain stepping
+        let mut simulation = Simulation::new(&grid, 0, rho);
+        simulation.run(2);
+        simulation.fast_forward(11);
+        let mut stepped = Simulation::new(&grid, 0, rho);
+        stepped.run(13);
+        assert_eq!(simulation.state, stepped.state);
+    }
+}
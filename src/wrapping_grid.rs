@@ -0,0 +1,109 @@
+use crate::search::Neighborhood;
+use crate::{Cardinal, Grid};
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Display;
+
+// A view over a Grid where coordinates wrap around the edges, as on a torus
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct WrappingGrid<'a, T> {
+    pub grid: &'a Grid<T>,
+}
+
+impl<'a, T: Default + Clone> WrappingGrid<'a, T> {
+    pub fn new(grid: &'a Grid<T>) -> WrappingGrid<'a, T> {
+        WrappingGrid { grid }
+    }
+
+    pub fn wrap(&self, pos: (isize, isize)) -> (usize, usize) {
+        (
+            pos.0.rem_euclid(self.grid.width as isize) as usize,
+            pos.1.rem_euclid(self.grid.height as isize) as usize,
+        )
+    }
+
+    pub fn at(&self, pos: (isize, isize)) -> &T {
+        self.grid.at(self.wrap(pos))
+    }
+
+    pub fn step(&self, pos: (usize, usize), delta: (isize, isize), steps: isize) -> (usize, usize) {
+        self.wrap((
+            pos.0 as isize + delta.0 * steps,
+            pos.1 as isize + delta.1 * steps,
+        ))
+    }
+
+    pub fn get_neighbor_position(&self, pos: (usize, usize), cardinal: Cardinal) -> (usize, usize) {
+        self.step(pos, cardinal.delta(), 1)
+    }
+
+    pub fn get_neighbor_at(&self, pos: (usize, usize), cardinal: Cardinal) -> &T {
+        self.grid.at(self.get_neighbor_position(pos, cardinal))
+    }
+
+    pub fn cardinal_neighbor_positions(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .iter()
+            .map(|delta| self.step(pos, *delta, 1))
+            .collect()
+    }
+
+    pub fn neighbor_positions(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        (-1..=1)
+            .cartesian_product(-1..=1)
+            .filter(|delta| *delta != (0, 0))
+            .map(|delta| self.step(pos, delta, 1))
+            .collect()
+    }
+
+    pub fn neighbors(&self, pos: (usize, usize)) -> impl Iterator<Item = &T> {
+        self.neighbor_positions(pos)
+            .into_iter()
+            .map(move |p| self.grid.at(p))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.grid.positions()
+    }
+}
+
+impl<T: Default + Clone> Neighborhood for WrappingGrid<'_, T> {
+    type Pos = (usize, usize);
+
+    fn adjacent(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        self.cardinal_neighbor_positions(pos)
+    }
+}
+
+impl<T: Display> Display for WrappingGrid<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+impl<T: Default + Clone> Grid<T> {
+    pub fn wrapping(&self) -> WrappingGrid<'_, T> {
+        WrappingGrid::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cardinal, Grid};
+
+    #[test]
+    fn negative_coordinates_wrap_around() {
+        let grid = Grid::from_lines(&["abc", "def"], &|c| c);
+        let wrapping = grid.wrapping();
+        assert_eq!(wrapping.wrap((-1, -1)), (2, 1));
+        assert_eq!(wrapping.wrap((-7, 5)), (2, 1));
+        assert_eq!(*wrapping.at((-3, -2)), 'a');
+        assert_eq!(wrapping.step((0, 0), (-1, 1), 4), (2, 0));
+        assert_eq!(
+            wrapping.get_neighbor_position((0, 0), Cardinal::North),
+            (0, 1)
+        );
+        assert_eq!(*wrapping.get_neighbor_at((0, 1), Cardinal::West), 'f');
+        assert_eq!(wrapping.neighbor_positions((0, 0)).len(), 8);
+    }
+}